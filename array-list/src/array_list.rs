@@ -1,60 +1,575 @@
+use std::{
+    fmt::Debug,
+    mem::MaybeUninit,
+    ops::{Bound, Deref, DerefMut, Index, IndexMut, RangeBounds},
+    ptr,
+    slice::{self, SliceIndex},
+};
+
 use anyhow::{anyhow, Error};
 
-#[allow(dead_code)]
-struct ArrayList<T> {
+use crate::growth::{AllocStats, GrowthPolicy};
+
+/// ArrayList is a growable array.
+/// Only the first `length` slots of `arr` are initialized, the rest is spare capacity.
+pub struct ArrayList<T> {
     length: usize,
     capacity: usize,
-    arr: Box<[T]>,
+    min_capacity: usize,
+    arr: Box<[MaybeUninit<T>]>,
+    policy: GrowthPolicy,
+    stats: AllocStats,
 }
 
 const INITIAL_CAP: usize = 5;
 
-#[allow(dead_code)]
-impl<T> ArrayList<T>
-where
-    T: Copy + Default,
-{
-    fn new() -> Self {
+impl<T> ArrayList<T> {
+    pub fn new() -> Self {
+        Self::with_capacity(INITIAL_CAP)
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::with_capacity_and_policy(capacity, GrowthPolicy::default())
+    }
+
+    pub fn with_policy(policy: GrowthPolicy) -> Self {
+        Self::with_capacity_and_policy(INITIAL_CAP, policy)
+    }
+
+    /// The list never shrinks below the given capacity on its own,
+    /// only `shrink_to_fit` goes below it.
+    pub fn with_capacity_and_policy(capacity: usize, policy: GrowthPolicy) -> Self {
         Self {
             length: 0,
-            capacity: INITIAL_CAP,
-            arr: vec![T::default(); INITIAL_CAP].into_boxed_slice(),
+            capacity,
+            min_capacity: capacity,
+            arr: Box::new_uninit_slice(capacity),
+            policy,
+            stats: AllocStats::default(),
         }
     }
 
-    fn push(&mut self, val: T) {
-        if self.length == self.capacity {
-            let new_capacity = self.capacity * 2;
-            let mut new_arr = vec![T::default(); new_capacity].into_boxed_slice();
-            new_arr[..self.capacity].copy_from_slice(&self.arr);
-            self.arr = new_arr;
-            self.capacity = new_capacity;
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn stats(&self) -> AllocStats {
+        self.stats
+    }
+
+    /// Makes sure that `additional` more elements fit without reallocating.
+    pub fn reserve(&mut self, additional: usize) {
+        let required = self.length + additional;
+        if required > self.capacity {
+            self.reallocate(self.policy.next_capacity(self.capacity, required));
         }
+    }
+
+    fn base(&mut self) -> *mut T {
+        self.arr.as_mut_ptr().cast()
+    }
 
-        self.arr[self.length] = val;
+    fn reallocate(&mut self, new_capacity: usize) {
+        let mut new_arr = Box::new_uninit_slice(new_capacity);
+        // SAFETY: the first `length` slots are initialized and get moved bitwise,
+        // the old slots are treated as uninitialized afterwards.
+        unsafe {
+            ptr::copy_nonoverlapping(self.arr.as_ptr(), new_arr.as_mut_ptr(), self.length);
+        }
+        self.arr = new_arr;
+        self.capacity = new_capacity;
+        self.stats.record::<T>(self.length);
+    }
+
+    pub fn push(&mut self, val: T) {
+        self.reserve(1);
+
+        self.arr[self.length].write(val);
         self.length += 1;
     }
 
-    fn at(&self, i: usize) -> Result<T, Error> {
+    /// Halves the capacity until the list fills at least a quarter of it again.
+    /// Never shrinks below the initial capacity.
+    fn shrink_if_sparse(&mut self) {
+        let mut new_capacity = self.capacity;
+        while new_capacity > self.min_capacity && self.length <= new_capacity / 4 {
+            new_capacity = (new_capacity / 2).max(self.min_capacity);
+        }
+
+        if new_capacity != self.capacity {
+            self.reallocate(new_capacity);
+        }
+    }
+
+    pub fn shrink_to_fit(&mut self) {
+        if self.length < self.capacity {
+            self.reallocate(self.length);
+        }
+    }
+
+    pub fn at(&self, i: usize) -> Result<&T, Error> {
         if i >= self.length {
             return Err(anyhow!("index {i} out of bounds"));
         }
 
-        Ok(self.arr[i])
+        // SAFETY: slots below `length` are initialized
+        Ok(unsafe { self.arr[i].assume_init_ref() })
     }
 
-    fn pop(&mut self) -> Result<T, Error> {
+    pub fn pop(&mut self) -> Result<T, Error> {
         if self.length == 0 {
             return Err(anyhow!("empty list"));
         }
 
         self.length -= 1;
-        Ok(self.arr[self.length])
+        // SAFETY: the slot was initialized and is now outside of `length`,
+        // so it is never read again
+        let val = unsafe { self.arr[self.length].assume_init_read() };
+        self.shrink_if_sparse();
+        Ok(val)
+    }
+
+    pub fn insert(&mut self, i: usize, val: T) -> Result<(), Error> {
+        if i > self.length {
+            return Err(anyhow!("index {i} out of bounds"));
+        }
+
+        self.reserve(1);
+
+        let base = self.base();
+        // SAFETY: there is room for one more element, the tail gets moved one slot
+        // to the right and the hole is filled with `val`
+        unsafe {
+            ptr::copy(base.add(i), base.add(i + 1), self.length - i);
+            ptr::write(base.add(i), val);
+        }
+        self.length += 1;
+        Ok(())
+    }
+
+    pub fn remove(&mut self, i: usize) -> Result<T, Error> {
+        if i >= self.length {
+            return Err(anyhow!("index {i} out of bounds"));
+        }
+
+        let base = self.base();
+        // SAFETY: the value is moved out and the tail is moved one slot to the left
+        // over the hole
+        let val = unsafe {
+            let val = ptr::read(base.add(i));
+            ptr::copy(base.add(i + 1), base.add(i), self.length - i - 1);
+            val
+        };
+        self.length -= 1;
+        self.shrink_if_sparse();
+        Ok(val)
+    }
+
+    /// Removes the element at `i` and fills the hole with the last element.
+    /// This does not preserve the order, but is O(1).
+    pub fn swap_remove(&mut self, i: usize) -> Result<T, Error> {
+        if i >= self.length {
+            return Err(anyhow!("index {i} out of bounds"));
+        }
+
+        self.length -= 1;
+        let base = self.base();
+        // SAFETY: the value is moved out and the former last element moved into the hole
+        let val = unsafe {
+            let val = ptr::read(base.add(i));
+            ptr::copy(base.add(self.length), base.add(i), 1);
+            val
+        };
+        self.shrink_if_sparse();
+        Ok(val)
+    }
+
+    pub fn truncate(&mut self, len: usize) {
+        if len >= self.length {
+            return;
+        }
+
+        let tail = ptr::slice_from_raw_parts_mut(
+            // SAFETY: `len` is within the initialized part
+            unsafe { self.base().add(len) },
+            self.length - len,
+        );
+        // shorten first, so a panicking drop leaks the rest instead of dropping twice
+        self.length = len;
+        // SAFETY: the tail is initialized and no longer part of the list
+        unsafe { ptr::drop_in_place(tail) };
+        self.shrink_if_sparse();
+    }
+
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    /// Keeps only the elements for which `keep` returns true, in their original order.
+    pub fn retain<F>(&mut self, mut keep: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let len = self.length;
+        // if `keep` panics, the remaining elements are leaked instead of dropped twice
+        self.length = 0;
+
+        let base = self.base();
+        let mut kept = 0;
+        for i in 0..len {
+            // SAFETY: slots from `i` on are still initialized, slots below `kept` are
+            // filled with the kept elements
+            unsafe {
+                let curr = base.add(i);
+                if keep(&*curr) {
+                    ptr::copy(curr, base.add(kept), 1);
+                    kept += 1;
+                } else {
+                    ptr::drop_in_place(curr);
+                }
+            }
+        }
+
+        self.length = kept;
+        self.shrink_if_sparse();
+    }
+
+    /// Removes consecutive elements for which `same(curr, prev)` returns true,
+    /// where `prev` is the last element that was kept.
+    pub fn dedup_by<F>(&mut self, mut same: F)
+    where
+        F: FnMut(&mut T, &mut T) -> bool,
+    {
+        let len = self.length;
+        if len <= 1 {
+            return;
+        }
+        // if `same` panics, the remaining elements are leaked instead of dropped twice
+        self.length = 0;
+
+        let base = self.base();
+        let mut kept = 1;
+        for i in 1..len {
+            // SAFETY: same as in `retain`, `kept - 1 < i` so the references don't alias
+            unsafe {
+                let curr = base.add(i);
+                if same(&mut *curr, &mut *base.add(kept - 1)) {
+                    ptr::drop_in_place(curr);
+                } else {
+                    ptr::copy(curr, base.add(kept), 1);
+                    kept += 1;
+                }
+            }
+        }
+
+        self.length = kept;
+        self.shrink_if_sparse();
+    }
+
+    pub fn dedup(&mut self)
+    where
+        T: PartialEq,
+    {
+        self.dedup_by(|curr, prev| curr == prev)
+    }
+
+    /// Removes the elements in `range` and returns them as an iterator.
+    /// The elements are removed even if the iterator is not fully consumed.
+    pub fn drain<R>(&mut self, range: R) -> Result<Drain<'_, T>, Error>
+    where
+        R: RangeBounds<usize>,
+    {
+        let start = match range.start_bound() {
+            Bound::Included(&i) => i,
+            Bound::Excluded(&i) => i + 1,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&i) => i + 1,
+            Bound::Excluded(&i) => i,
+            Bound::Unbounded => self.length,
+        };
+        if start > end || end > self.length {
+            return Err(anyhow!("range {start}..{end} out of bounds"));
+        }
+
+        let tail_len = self.length - end;
+        // the drained and the tail elements are owned by `Drain` until it is dropped
+        self.length = start;
+        Ok(Drain {
+            list: self,
+            curr: start,
+            end,
+            tail: end,
+            tail_len,
+        })
+    }
+}
+
+/// Drain moves a range of elements out of an ArrayList.
+/// When it is dropped, the elements behind the range are moved to close the gap.
+pub struct Drain<'a, T> {
+    list: &'a mut ArrayList<T>,
+    curr: usize, // including
+    end: usize,  // excluding
+    tail: usize,
+    tail_len: usize,
+}
+
+impl<T> Iterator for Drain<'_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.curr == self.end {
+            return None;
+        }
+
+        self.curr += 1;
+        // SAFETY: slots between `curr` and `end` are initialized and owned by the drain
+        Some(unsafe { self.list.arr[self.curr - 1].assume_init_read() })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end - self.curr;
+        (len, Some(len))
+    }
+}
+
+impl<T> DoubleEndedIterator for Drain<'_, T> {
+    fn next_back(&mut self) -> Option<T> {
+        if self.curr == self.end {
+            return None;
+        }
+
+        self.end -= 1;
+        // SAFETY: slots between `curr` and `end` are initialized and owned by the drain
+        Some(unsafe { self.list.arr[self.end].assume_init_read() })
+    }
+}
+
+impl<T> ExactSizeIterator for Drain<'_, T> {}
+
+impl<T> Drop for Drain<'_, T> {
+    fn drop(&mut self) {
+        for slot in &mut self.list.arr[self.curr..self.end] {
+            // SAFETY: slots between `curr` and `end` were not moved out yet
+            unsafe { slot.assume_init_drop() };
+        }
+
+        let start = self.list.length;
+        let base = self.list.base();
+        // SAFETY: the tail is initialized and gets moved right behind the kept front
+        unsafe { ptr::copy(base.add(self.tail), base.add(start), self.tail_len) };
+        self.list.length = start + self.tail_len;
+        self.list.shrink_if_sparse();
+    }
+}
+
+impl<T> Deref for ArrayList<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        // SAFETY: slots below `length` are initialized
+        unsafe { slice::from_raw_parts(self.arr.as_ptr().cast(), self.length) }
+    }
+}
+
+impl<T> DerefMut for ArrayList<T> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        // SAFETY: slots below `length` are initialized
+        unsafe { slice::from_raw_parts_mut(self.arr.as_mut_ptr().cast(), self.length) }
+    }
+}
+
+impl<T, I> Index<I> for ArrayList<T>
+where
+    I: SliceIndex<[T]>,
+{
+    type Output = I::Output;
+
+    fn index(&self, index: I) -> &Self::Output {
+        &(**self)[index]
+    }
+}
+
+impl<T, I> IndexMut<I> for ArrayList<T>
+where
+    I: SliceIndex<[T]>,
+{
+    fn index_mut(&mut self, index: I) -> &mut Self::Output {
+        &mut (**self)[index]
+    }
+}
+
+impl<T> Extend<T> for ArrayList<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+
+        for val in iter {
+            self.push(val);
+        }
+    }
+}
+
+impl<T> FromIterator<T> for ArrayList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut a = Self::new();
+        a.extend(iter);
+        a
+    }
+}
+
+impl<T> Clone for ArrayList<T>
+where
+    T: Clone,
+{
+    fn clone(&self) -> Self {
+        let mut a = Self::with_capacity_and_policy(self.length, self.policy);
+        a.min_capacity = self.min_capacity;
+        a.extend(self.iter().cloned());
+        a
+    }
+}
+
+impl<T> Debug for ArrayList<T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T, U> PartialEq<U> for ArrayList<T>
+where
+    T: PartialEq,
+    U: AsRef<[T]>,
+{
+    fn eq(&self, other: &U) -> bool {
+        **self == *other.as_ref()
+    }
+}
+
+impl<T> Eq for ArrayList<T> where T: Eq {}
+
+impl<T> AsRef<[T]> for ArrayList<T> {
+    fn as_ref(&self) -> &[T] {
+        self
+    }
+}
+
+/// IntoIter moves the values out of an ArrayList from front to back.
+/// Values that are not consumed get dropped together with the iterator.
+pub struct IntoIter<T> {
+    start: usize, // including
+    end: usize,   // excluding
+    arr: Box<[MaybeUninit<T>]>,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.start == self.end {
+            return None;
+        }
+
+        self.start += 1;
+        // SAFETY: slots between `start` and `end` are initialized
+        Some(unsafe { self.arr[self.start - 1].assume_init_read() })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end - self.start;
+        (len, Some(len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<T> {
+        if self.start == self.end {
+            return None;
+        }
+
+        self.end -= 1;
+        // SAFETY: slots between `start` and `end` are initialized
+        Some(unsafe { self.arr[self.end].assume_init_read() })
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> Drop for IntoIter<T> {
+    fn drop(&mut self) {
+        for slot in &mut self.arr[self.start..self.end] {
+            // SAFETY: slots between `start` and `end` are initialized
+            unsafe { slot.assume_init_drop() };
+        }
+    }
+}
+
+impl<T> IntoIterator for ArrayList<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(mut self) -> IntoIter<T> {
+        let end = self.length;
+        // the values now belong to the iterator
+        self.length = 0;
+        IntoIter {
+            start: 0,
+            end,
+            arr: std::mem::take(&mut self.arr),
+        }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a ArrayList<T> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut ArrayList<T> {
+    type Item = &'a mut T;
+    type IntoIter = slice::IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<T> Default for ArrayList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for ArrayList<T> {
+    fn drop(&mut self) {
+        for slot in &mut self.arr[..self.length] {
+            // SAFETY: slots below `length` are initialized
+            unsafe { slot.assume_init_drop() };
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::rc::Rc;
+
     use super::*;
 
     #[test]
@@ -66,10 +581,10 @@ mod tests {
         a.push(2);
         a.push(3);
 
-        assert!(a.at(0).is_ok_and(|i| i == 0));
-        assert!(a.at(1).is_ok_and(|i| i == 1));
-        assert!(a.at(2).is_ok_and(|i| i == 2));
-        assert!(a.at(3).is_ok_and(|i| i == 3));
+        assert!(a.at(0).is_ok_and(|i| *i == 0));
+        assert!(a.at(1).is_ok_and(|i| *i == 1));
+        assert!(a.at(2).is_ok_and(|i| *i == 2));
+        assert!(a.at(3).is_ok_and(|i| *i == 3));
         assert!(a.at(4).is_err());
 
         assert!(a.pop().is_ok_and(|i| i == 3));
@@ -78,4 +593,263 @@ mod tests {
         assert!(a.pop().is_ok_and(|i| i == 0));
         assert!(a.pop().is_err());
     }
+
+    #[test]
+    fn non_copy_values() {
+        let mut a = ArrayList::new();
+        for i in 0..20 {
+            a.push(format!("value {i}"));
+        }
+
+        assert_eq!(a.len(), 20);
+        assert!(a.at(12).is_ok_and(|s| s == "value 12"));
+        assert!(a.pop().is_ok_and(|s| s == "value 19"));
+        assert_eq!(a.len(), 19);
+    }
+
+    #[test]
+    fn drops_remaining_values() {
+        let counter = Rc::new(());
+        let mut a = ArrayList::new();
+        for _ in 0..12 {
+            a.push(Rc::clone(&counter));
+        }
+        assert_eq!(Rc::strong_count(&counter), 13);
+
+        drop(a.pop());
+        assert_eq!(Rc::strong_count(&counter), 12);
+
+        drop(a);
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+
+    #[test]
+    fn index() {
+        let mut a: ArrayList<i32> = (0..10).collect();
+
+        assert_eq!(a[3], 3);
+        a[3] = 30;
+        assert_eq!(a[3], 30);
+        assert_eq!(a[1..3], [1, 2]);
+        assert_eq!(a.len(), 10);
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_out_of_bounds() {
+        let a: ArrayList<i32> = (0..3).collect();
+        let _ = a[3];
+    }
+
+    #[test]
+    fn iterators() {
+        let mut a: ArrayList<i32> = (0..5).collect();
+
+        for v in &mut a {
+            *v *= 2;
+        }
+        assert_eq!((&a).into_iter().sum::<i32>(), 20);
+        assert_eq!(a.iter().next_back(), Some(&8));
+
+        let mut it = a.into_iter();
+        assert_eq!(it.len(), 5);
+        assert_eq!(it.next(), Some(0));
+        assert_eq!(it.next_back(), Some(8));
+        assert_eq!(it.collect::<Vec<_>>(), [2, 4, 6]);
+    }
+
+    #[test]
+    fn into_iter_drops_rest() {
+        let counter = Rc::new(());
+        let a: ArrayList<_> = (0..8).map(|_| Rc::clone(&counter)).collect();
+
+        let mut it = a.into_iter();
+        let first = it.next();
+        assert_eq!(Rc::strong_count(&counter), 9);
+
+        drop(it);
+        assert_eq!(Rc::strong_count(&counter), 2);
+        drop(first);
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+
+    #[test]
+    fn extend_and_slice_methods() {
+        let mut a = ArrayList::new();
+        a.extend([5, 3, 4]);
+        a.extend(vec![1, 2]);
+
+        a.sort();
+        assert_eq!(a, [1, 2, 3, 4, 5]);
+        assert_eq!(a.binary_search(&4), Ok(3));
+        assert!(a.contains(&2));
+        assert_eq!(a.clone(), a);
+        assert_eq!(format!("{a:?}"), "[1, 2, 3, 4, 5]");
+    }
+
+    #[test]
+    fn insert_remove() {
+        let mut a: ArrayList<i32> = (0..5).collect();
+
+        assert!(a.insert(2, 10).is_ok());
+        assert!(a.insert(6, 11).is_ok());
+        assert!(a.insert(0, 12).is_ok());
+        assert!(a.insert(9, 13).is_err());
+        assert_eq!(a, [12, 0, 1, 10, 2, 3, 4, 11]);
+
+        assert!(a.remove(3).is_ok_and(|i| i == 10));
+        assert!(a.remove(0).is_ok_and(|i| i == 12));
+        assert!(a.remove(6).is_err());
+        assert!(a.swap_remove(1).is_ok_and(|i| i == 1));
+        assert!(a.swap_remove(5).is_err());
+        assert_eq!(a, [0, 11, 2, 3, 4]);
+    }
+
+    #[test]
+    fn drain() {
+        let mut a: ArrayList<String> = (0..10).map(|i| i.to_string()).collect();
+
+        let drained: Vec<_> = a.drain(2..5).unwrap().collect();
+        assert_eq!(drained, ["2", "3", "4"]);
+        assert_eq!(a.join(","), "0,1,5,6,7,8,9");
+
+        let mut it = a.drain(4..).unwrap();
+        assert!(it.next_back().is_some_and(|s| s == "9"));
+        drop(it);
+        assert_eq!(a.join(","), "0,1,5,6");
+
+        assert!(a.drain(3..5).is_err());
+        assert_eq!(a.drain(..).unwrap().len(), 4);
+        assert!(a.is_empty());
+    }
+
+    #[test]
+    fn retain_dedup() {
+        let mut a: ArrayList<i32> = [1, 1, 2, 3, 3, 3, 4, 5, 5].into_iter().collect();
+
+        a.dedup();
+        assert_eq!(a, [1, 2, 3, 4, 5]);
+
+        a.retain(|i| i % 2 == 1);
+        assert_eq!(a, [1, 3, 5]);
+
+        let mut a: ArrayList<i32> = [1, 2, 4, 7, 8, 10].into_iter().collect();
+        a.dedup_by(|curr, prev| *curr - *prev <= 2);
+        assert_eq!(a, [1, 4, 7, 10]);
+    }
+
+    #[test]
+    fn retain_drops_removed() {
+        let counter = Rc::new(());
+        let mut a: ArrayList<_> = (0..10).map(|i| (i, Rc::clone(&counter))).collect();
+
+        a.retain(|(i, _)| *i < 3);
+        assert_eq!(a.len(), 3);
+        assert_eq!(Rc::strong_count(&counter), 4);
+    }
+
+    #[test]
+    fn shrinks() {
+        let mut a: ArrayList<i32> = (0..100).collect();
+        let capacity = a.capacity();
+
+        a.truncate(10);
+        assert_eq!(a.len(), 10);
+        assert!(a.capacity() < capacity);
+        assert!(a.capacity() >= 10);
+
+        a.shrink_to_fit();
+        assert_eq!(a.capacity(), 10);
+
+        a.clear();
+        assert!(a.is_empty());
+        assert_eq!(a.capacity(), INITIAL_CAP);
+
+        a.shrink_to_fit();
+        assert_eq!(a.capacity(), 0);
+        a.push(1);
+        assert_eq!(a, [1]);
+    }
+
+    #[test]
+    fn pop_shrinks() {
+        let mut a: ArrayList<i32> = (0..80).collect();
+        while a.len() > 1 {
+            assert!(a.pop().is_ok());
+            assert!(a.len() > a.capacity() / 4 || a.capacity() == INITIAL_CAP);
+        }
+        assert_eq!(a, [0]);
+    }
+
+    #[test]
+    fn growth_policy() {
+        let mut a = ArrayList::with_capacity_and_policy(4, GrowthPolicy::Increment(4));
+        for i in 0..10 {
+            a.push(i);
+        }
+        assert_eq!(a.capacity(), 12);
+
+        let mut a = ArrayList::with_capacity_and_policy(4, GrowthPolicy::OneAndHalf);
+        for i in 0..10 {
+            a.push(i);
+        }
+        assert_eq!(a.capacity(), 13);
+
+        let mut a = ArrayList::with_policy(GrowthPolicy::Custom(|c| c * 4));
+        for i in 0..10 {
+            a.push(i);
+        }
+        assert_eq!(a.capacity(), 20);
+        assert_eq!(a, [0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+    }
+
+    #[test]
+    fn clone_keeps_policy() {
+        let mut a = ArrayList::with_capacity_and_policy(2, GrowthPolicy::Increment(3));
+        a.extend(0..5);
+
+        let mut b = a.clone();
+        assert_eq!(b.capacity(), 5);
+        b.push(5);
+        assert_eq!(b.capacity(), 8);
+        b.extend(6..9);
+        assert_eq!(b.capacity(), 11);
+
+        while b.pop().is_ok() {}
+        assert_eq!(b.capacity(), 2);
+        assert_eq!(a, [0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn stats() {
+        let mut a = ArrayList::<u64>::with_capacity(2);
+        assert_eq!(a.stats(), AllocStats::default());
+
+        for i in 0..8 {
+            a.push(i);
+        }
+        // 2 -> 4 -> 8
+        assert_eq!(a.stats().reallocations, 2);
+        assert_eq!(a.stats().bytes_copied, (2 + 4) * 8);
+
+        a.truncate(1);
+        assert_eq!(a.capacity(), 2);
+        assert_eq!(a.stats().reallocations, 3);
+        assert_eq!(a.stats().bytes_copied, (2 + 4 + 1) * 8);
+    }
+
+    #[test]
+    fn reserve() {
+        let mut a = ArrayList::with_capacity(0);
+        a.reserve(100);
+        assert_eq!(a.capacity(), 100);
+
+        a.extend(0..100);
+        a.reserve(0);
+        assert_eq!(a.capacity(), 100);
+        assert_eq!(a.stats().reallocations, 1);
+
+        a.reserve(1);
+        assert_eq!(a.capacity(), 200);
+    }
 }
@@ -0,0 +1,464 @@
+use std::{
+    fmt::{Debug, Display},
+    sync::{Arc, Condvar, Mutex, MutexGuard},
+    time::{Duration, Instant},
+};
+
+use crate::ring_buffer::RingBuffer;
+
+/// SendError hands the value back to the sender.
+/// `send` only fails with `Closed`, `try_send` also with `Full`
+/// and `send_timeout` also with `Timeout`.
+#[derive(Debug, PartialEq, Eq)]
+pub enum SendError<T> {
+    Full(T),
+    Timeout(T),
+    Closed(T),
+}
+
+impl<T> SendError<T> {
+    pub fn into_inner(self) -> T {
+        match self {
+            Self::Full(val) | Self::Timeout(val) | Self::Closed(val) => val,
+        }
+    }
+}
+
+impl<T> Display for SendError<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Full(_) => f.write_str("queue full"),
+            Self::Timeout(_) => f.write_str("timed out waiting for space in queue"),
+            Self::Closed(_) => f.write_str("queue closed"),
+        }
+    }
+}
+
+impl<T> std::error::Error for SendError<T> where T: Debug {}
+
+/// RecvError tells why no value was received.
+/// `recv` only fails with `Closed`, `try_recv` also with `Empty`
+/// and `recv_timeout` also with `Timeout`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum RecvError {
+    Empty,
+    Timeout,
+    Closed,
+}
+
+impl Display for RecvError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Empty => f.write_str("queue empty"),
+            Self::Timeout => f.write_str("timed out waiting for a value"),
+            Self::Closed => f.write_str("queue closed and empty"),
+        }
+    }
+}
+
+impl std::error::Error for RecvError {}
+
+struct State<T> {
+    buf: RingBuffer<T>,
+    closed: bool,
+    senders: usize,
+    receivers: usize,
+}
+
+struct Shared<T> {
+    state: Mutex<State<T>>,
+    not_empty: Condvar,
+    not_full: Condvar,
+    capacity: usize,
+}
+
+impl<T> Shared<T> {
+    fn lock(&self) -> MutexGuard<'_, State<T>> {
+        // a panicking thread cannot leave the buffer half updated, so poisoning is ignored
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn close(&self) {
+        self.lock().closed = true;
+        self.not_empty.notify_all();
+        self.not_full.notify_all();
+    }
+
+    /// Waits on `cond` until `ready` returns true or the deadline passes.
+    fn wait_until<'a>(
+        &self,
+        cond: &Condvar,
+        mut state: MutexGuard<'a, State<T>>,
+        deadline: Option<Instant>,
+        ready: fn(&State<T>, usize) -> bool,
+    ) -> (MutexGuard<'a, State<T>>, bool) {
+        while !ready(&state, self.capacity) {
+            state = match deadline {
+                None => cond.wait(state).unwrap_or_else(|e| e.into_inner()),
+                Some(deadline) => {
+                    let now = Instant::now();
+                    if now >= deadline {
+                        return (state, false);
+                    }
+                    cond.wait_timeout(state, deadline - now)
+                        .unwrap_or_else(|e| e.into_inner())
+                        .0
+                }
+            };
+        }
+        (state, true)
+    }
+
+    fn send(&self, val: T, deadline: Option<Instant>) -> Result<(), SendError<T>> {
+        let state = self.lock();
+        let (mut state, ready) = self.wait_until(&self.not_full, state, deadline, |s, cap| {
+            s.closed || s.buf.len() < cap
+        });
+
+        if state.closed {
+            return Err(SendError::Closed(val));
+        }
+        if !ready {
+            return Err(SendError::Timeout(val));
+        }
+
+        state.buf.push_back(val);
+        drop(state);
+        self.not_empty.notify_one();
+        Ok(())
+    }
+
+    fn recv(&self, deadline: Option<Instant>) -> Result<T, RecvError> {
+        let state = self.lock();
+        let (mut state, ready) = self.wait_until(&self.not_empty, state, deadline, |s, _| {
+            s.closed || !s.buf.is_empty()
+        });
+
+        // values that were sent before closing can still be received
+        let val = match state.buf.pop_front() {
+            Some(val) => val,
+            None if state.closed => return Err(RecvError::Closed),
+            None if !ready => return Err(RecvError::Timeout),
+            None => unreachable!("the buffer is not empty when ready"),
+        };
+        drop(state);
+        self.not_full.notify_one();
+        Ok(val)
+    }
+}
+
+/// Creates a bounded queue that holds at most `capacity` values.
+/// Senders block while it is full and receivers block while it is empty.
+/// The queue is closed once all senders or all receivers are dropped,
+/// or when either side calls `close`.
+///
+/// # Panics
+/// If `capacity` is zero.
+pub fn bounded<T>(capacity: usize) -> (Sender<T>, Receiver<T>) {
+    assert!(capacity > 0, "capacity has to be at least one");
+
+    let shared = Arc::new(Shared {
+        state: Mutex::new(State {
+            buf: RingBuffer::with_capacity(capacity),
+            closed: false,
+            senders: 1,
+            receivers: 1,
+        }),
+        not_empty: Condvar::new(),
+        not_full: Condvar::new(),
+        capacity,
+    });
+
+    (
+        Sender {
+            shared: Arc::clone(&shared),
+        },
+        Receiver { shared },
+    )
+}
+
+pub struct Sender<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> Sender<T> {
+    /// Blocks until there is space in the queue.
+    pub fn send(&self, val: T) -> Result<(), SendError<T>> {
+        self.shared.send(val, None)
+    }
+
+    pub fn try_send(&self, val: T) -> Result<(), SendError<T>> {
+        match self.shared.send(val, Some(Instant::now())) {
+            Err(SendError::Timeout(val)) => Err(SendError::Full(val)),
+            res => res,
+        }
+    }
+
+    /// A timeout past the range of `Instant`, like `Duration::MAX`, waits like `send`.
+    pub fn send_timeout(&self, val: T, timeout: Duration) -> Result<(), SendError<T>> {
+        self.shared.send(val, Instant::now().checked_add(timeout))
+    }
+
+    pub fn close(&self) {
+        self.shared.close()
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.shared.lock().closed
+    }
+
+    pub fn len(&self) -> usize {
+        self.shared.lock().buf.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.shared.capacity
+    }
+}
+
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Self {
+        self.shared.lock().senders += 1;
+        Self {
+            shared: Arc::clone(&self.shared),
+        }
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        let mut state = self.shared.lock();
+        state.senders -= 1;
+        if state.senders == 0 {
+            drop(state);
+            self.shared.close();
+        }
+    }
+}
+
+pub struct Receiver<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> Receiver<T> {
+    /// Blocks until there is a value in the queue.
+    /// After the queue is closed, the remaining values are still received.
+    pub fn recv(&self) -> Result<T, RecvError> {
+        self.shared.recv(None)
+    }
+
+    pub fn try_recv(&self) -> Result<T, RecvError> {
+        match self.shared.recv(Some(Instant::now())) {
+            Err(RecvError::Timeout) => Err(RecvError::Empty),
+            res => res,
+        }
+    }
+
+    /// Waits without a deadline if `timeout` overflows an `Instant`.
+    pub fn recv_timeout(&self, timeout: Duration) -> Result<T, RecvError> {
+        self.shared.recv(Instant::now().checked_add(timeout))
+    }
+
+    pub fn close(&self) {
+        self.shared.close()
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.shared.lock().closed
+    }
+
+    pub fn len(&self) -> usize {
+        self.shared.lock().buf.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.shared.capacity
+    }
+}
+
+impl<T> Clone for Receiver<T> {
+    fn clone(&self) -> Self {
+        self.shared.lock().receivers += 1;
+        Self {
+            shared: Arc::clone(&self.shared),
+        }
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        let mut state = self.shared.lock();
+        state.receivers -= 1;
+        if state.receivers == 0 {
+            drop(state);
+            self.shared.close();
+        }
+    }
+}
+
+/// Iterates over received values until the queue is closed and drained.
+impl<T> Iterator for Receiver<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.recv().ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashSet, sync::mpsc, thread};
+
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        let (s, r) = bounded(2);
+
+        assert!(s.send(0).is_ok());
+        assert!(s.try_send(1).is_ok());
+        assert_eq!(s.try_send(2), Err(SendError::Full(2)));
+        assert_eq!(s.len(), 2);
+
+        assert_eq!(r.recv(), Ok(0));
+        assert_eq!(r.try_recv(), Ok(1));
+        assert_eq!(r.try_recv(), Err(RecvError::Empty));
+    }
+
+    #[test]
+    fn timeouts() {
+        let (s, r) = bounded(1);
+        let timeout = Duration::from_millis(10);
+
+        assert_eq!(r.recv_timeout(timeout), Err(RecvError::Timeout));
+        assert!(s.send_timeout(0, timeout).is_ok());
+        assert_eq!(s.send_timeout(1, timeout), Err(SendError::Timeout(1)));
+        assert_eq!(r.recv_timeout(timeout), Ok(0));
+
+        assert!(s.send_timeout(2, Duration::MAX).is_ok());
+        assert_eq!(r.recv_timeout(Duration::MAX), Ok(2));
+        let receiver = thread::spawn(move || r.recv_timeout(Duration::MAX));
+        s.close();
+        assert_eq!(receiver.join().unwrap(), Err(RecvError::Closed));
+    }
+
+    #[test]
+    fn close_drains() {
+        let (s, r) = bounded(4);
+
+        assert!(s.send(0).is_ok());
+        assert!(s.send(1).is_ok());
+        r.close();
+        assert!(s.is_closed());
+        assert_eq!(s.send(2), Err(SendError::Closed(2)));
+
+        assert_eq!(r.recv(), Ok(0));
+        assert_eq!(r.try_recv(), Ok(1));
+        assert_eq!(r.recv(), Err(RecvError::Closed));
+        assert_eq!(r.try_recv(), Err(RecvError::Closed));
+    }
+
+    #[test]
+    fn disconnect() {
+        let (s, r) = bounded(4);
+        let s2 = s.clone();
+
+        assert!(s.send(0).is_ok());
+        drop(s);
+        assert!(!r.is_closed());
+        assert!(s2.send(1).is_ok());
+        drop(s2);
+        assert_eq!(r.collect::<Vec<_>>(), [0, 1]);
+
+        let (s, r) = bounded(4);
+        drop(r);
+        assert_eq!(s.send(0), Err(SendError::Closed(0)));
+    }
+
+    #[test]
+    fn unblocks_on_close() {
+        let (s, r) = bounded::<i32>(1);
+
+        let receiver = thread::spawn(move || r.recv());
+        thread::sleep(Duration::from_millis(10));
+        s.close();
+        assert_eq!(receiver.join().unwrap(), Err(RecvError::Closed));
+    }
+
+    #[test]
+    fn backpressure() {
+        let (s, r) = bounded(1);
+        let (full_tx, full_rx) = mpsc::channel();
+
+        let sender = thread::spawn(move || {
+            s.send(0).unwrap();
+            assert_eq!(s.try_send(1), Err(SendError::Full(1)));
+            full_tx.send(()).unwrap();
+            // blocks until the receiver makes room
+            for i in 1..3 {
+                s.send(i).unwrap();
+            }
+        });
+        full_rx.recv().unwrap();
+        assert_eq!(r.len(), 1);
+        assert_eq!(r.collect::<Vec<_>>(), [0, 1, 2]);
+        sender.join().unwrap();
+    }
+
+    #[test]
+    fn threads() {
+        let producers = 4;
+        let consumers = 4;
+        let n = 10_000;
+        let (s, r) = bounded(16);
+
+        let senders: Vec<_> = (0..producers)
+            .map(|p| {
+                let s = s.clone();
+                thread::spawn(move || {
+                    for i in 0..n {
+                        s.send((p, i)).unwrap();
+                    }
+                })
+            })
+            .collect();
+        drop(s);
+
+        let receivers: Vec<_> = (0..consumers)
+            .map(|_| {
+                let r = r.clone();
+                thread::spawn(move || {
+                    // values of one producer arrive in order at every consumer
+                    let mut last = vec![None; producers];
+                    let mut received = Vec::new();
+                    for (p, i) in r {
+                        assert!(last[p] < Some(i));
+                        last[p] = Some(i);
+                        received.push((p, i));
+                    }
+                    received
+                })
+            })
+            .collect();
+        drop(r);
+
+        for sender in senders {
+            sender.join().unwrap();
+        }
+        let mut all = HashSet::new();
+        for receiver in receivers {
+            for val in receiver.join().unwrap() {
+                assert!(all.insert(val));
+            }
+        }
+        assert_eq!(all.len(), producers * n);
+    }
+}
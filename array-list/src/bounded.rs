@@ -0,0 +1,177 @@
+use crate::ring_buffer::{IntoIter, Iter, RingBuffer};
+
+/// BoundedRingBuffer keeps the last `capacity` elements that were pushed.
+/// Once it is full, every push evicts the oldest element instead of growing.
+#[derive(Debug)]
+pub struct BoundedRingBuffer<T> {
+    buf: RingBuffer<T>,
+}
+
+impl<T> BoundedRingBuffer<T> {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            buf: RingBuffer::with_capacity(capacity),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.buf.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.buf.is_empty()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.buf.len() == self.buf.capacity()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.buf.capacity()
+    }
+
+    /// Appends `val` as the newest element and returns the evicted oldest element,
+    /// if the buffer was full.
+    pub fn push(&mut self, val: T) -> Option<T> {
+        if self.capacity() == 0 {
+            return Some(val);
+        }
+
+        let evicted = if self.is_full() {
+            self.buf.pop_front()
+        } else {
+            None
+        };
+        self.buf.push_back(val);
+        evicted
+    }
+
+    /// Removes the oldest element.
+    pub fn pop_oldest(&mut self) -> Option<T> {
+        self.buf.pop_front()
+    }
+
+    pub fn oldest(&self) -> Option<&T> {
+        self.buf.front()
+    }
+
+    pub fn newest(&self) -> Option<&T> {
+        self.buf.back()
+    }
+
+    /// Returns the `i`-th oldest element.
+    pub fn get(&self, i: usize) -> Option<&T> {
+        self.buf.get(i)
+    }
+
+    pub fn clear(&mut self) {
+        self.buf.clear()
+    }
+
+    /// Iterates from the oldest to the newest element.
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.buf.iter()
+    }
+
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        self.buf.as_slices()
+    }
+}
+
+/// `RingBuffer::clone` only allocates for the current length,
+/// but the capacity is the bound of the clone.
+impl<T> Clone for BoundedRingBuffer<T>
+where
+    T: Clone,
+{
+    fn clone(&self) -> Self {
+        let mut buf = RingBuffer::with_capacity(self.capacity());
+        buf.extend(self.iter().cloned());
+        Self { buf }
+    }
+}
+
+impl<T> IntoIterator for BoundedRingBuffer<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        self.buf.into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a BoundedRingBuffer<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evicts_oldest() {
+        let mut b = BoundedRingBuffer::new(3);
+
+        assert_eq!(b.push(0), None);
+        assert_eq!(b.push(1), None);
+        assert!(!b.is_full());
+        assert_eq!(b.push(2), None);
+        assert!(b.is_full());
+
+        assert_eq!(b.push(3), Some(0));
+        assert_eq!(b.push(4), Some(1));
+        assert_eq!(b.len(), 3);
+        assert_eq!(b.capacity(), 3);
+        assert_eq!(b.oldest(), Some(&2));
+        assert_eq!(b.newest(), Some(&4));
+    }
+
+    #[test]
+    fn iterates_oldest_to_newest() {
+        let mut b = BoundedRingBuffer::new(4);
+        for i in 0..10 {
+            b.push(i.to_string());
+        }
+
+        assert_eq!(b.iter().cloned().collect::<Vec<_>>(), ["6", "7", "8", "9"]);
+        assert_eq!(b.get(1).map(String::as_str), Some("7"));
+        assert_eq!(b.pop_oldest().as_deref(), Some("6"));
+        assert!(!b.is_full());
+        assert_eq!(b.push("10".to_string()), None);
+        assert_eq!(b.into_iter().collect::<Vec<_>>(), ["7", "8", "9", "10"]);
+    }
+
+    #[test]
+    fn zero_capacity() {
+        let mut b = BoundedRingBuffer::new(0);
+
+        assert!(b.is_full());
+        assert_eq!(b.push(1), Some(1));
+        assert!(b.is_empty());
+    }
+
+    #[test]
+    fn clone_keeps_capacity() {
+        let mut b = BoundedRingBuffer::new(3);
+        b.push(1);
+
+        let mut c = b.clone();
+        assert_eq!(c.capacity(), 3);
+        assert_eq!(c.push(2), None);
+        assert_eq!(c.push(3), None);
+        assert_eq!(c.push(4), Some(1));
+        assert_eq!(c.iter().copied().collect::<Vec<_>>(), [2, 3, 4]);
+        assert_eq!(b.iter().copied().collect::<Vec<_>>(), [1]);
+
+        let mut empty = BoundedRingBuffer::<i32>::new(2).clone();
+        assert_eq!(empty.capacity(), 2);
+        assert_eq!(empty.push(1), None);
+        assert_eq!(empty.push(2), None);
+        assert_eq!(empty.push(3), Some(1));
+    }
+}
@@ -0,0 +1,445 @@
+use std::{
+    fmt::Debug,
+    iter::Chain,
+    mem::MaybeUninit,
+    ops::{Bound, RangeBounds},
+    ptr, slice,
+};
+
+use anyhow::{anyhow, Error};
+
+use crate::growth::{AllocStats, GrowthPolicy};
+
+/// GapBuffer is a growable array with a gap of uninitialized slots at the cursor.
+/// The elements before the cursor are at the start of `arr`, the ones after it at the end,
+/// so inserting and deleting at the cursor is O(1) and moving the cursor
+/// only moves the elements it passes over.
+pub struct GapBuffer<T> {
+    gap_start: usize,
+    gap_end: usize,
+    capacity: usize,
+    arr: Box<[MaybeUninit<T>]>,
+    policy: GrowthPolicy,
+    stats: AllocStats,
+}
+
+const INITIAL_CAP: usize = 16;
+
+impl<T> GapBuffer<T> {
+    pub fn new() -> Self {
+        Self::with_capacity(INITIAL_CAP)
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::with_capacity_and_policy(capacity, GrowthPolicy::default())
+    }
+
+    pub fn with_policy(policy: GrowthPolicy) -> Self {
+        Self::with_capacity_and_policy(INITIAL_CAP, policy)
+    }
+
+    pub fn with_capacity_and_policy(capacity: usize, policy: GrowthPolicy) -> Self {
+        Self {
+            gap_start: 0,
+            gap_end: capacity,
+            capacity,
+            arr: Box::new_uninit_slice(capacity),
+            policy,
+            stats: AllocStats::default(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.capacity - (self.gap_end - self.gap_start)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn stats(&self) -> AllocStats {
+        self.stats
+    }
+
+    /// Index of the element after the cursor, which is where the next insert goes.
+    pub fn cursor(&self) -> usize {
+        self.gap_start
+    }
+
+    /// Makes sure that `additional` more elements fit without reallocating.
+    pub fn reserve(&mut self, additional: usize) {
+        let required = self.len() + additional;
+        if required > self.capacity {
+            self.reallocate(self.policy.next_capacity(self.capacity, required));
+        }
+    }
+
+    fn base(&mut self) -> *mut T {
+        self.arr.as_mut_ptr().cast()
+    }
+
+    fn reallocate(&mut self, new_capacity: usize) {
+        let back_len = self.capacity - self.gap_end;
+        let new_gap_end = new_capacity - back_len;
+        let mut new_arr = Box::new_uninit_slice(new_capacity);
+        // SAFETY: both sides of the gap are initialized and get moved bitwise to the
+        // start and the end of the new allocation, the old slots are treated as
+        // uninitialized afterwards.
+        unsafe {
+            ptr::copy_nonoverlapping(self.arr.as_ptr(), new_arr.as_mut_ptr(), self.gap_start);
+            ptr::copy_nonoverlapping(
+                self.arr.as_ptr().add(self.gap_end),
+                new_arr.as_mut_ptr().add(new_gap_end),
+                back_len,
+            );
+        }
+        self.arr = new_arr;
+        self.gap_end = new_gap_end;
+        self.capacity = new_capacity;
+        self.stats.record::<T>(self.len());
+    }
+
+    pub fn shrink_to_fit(&mut self) {
+        if self.len() < self.capacity {
+            self.reallocate(self.len());
+        }
+    }
+
+    /// Moves the cursor in front of the element at `i`, or to the end for `i == len`.
+    pub fn set_cursor(&mut self, i: usize) -> Result<(), Error> {
+        if i > self.len() {
+            return Err(anyhow!("index {i} out of bounds"));
+        }
+
+        let base = self.base();
+        if i < self.gap_start {
+            let n = self.gap_start - i;
+            // SAFETY: the `n` elements before the gap are moved to the end of the gap
+            unsafe { ptr::copy(base.add(i), base.add(self.gap_end - n), n) };
+            self.gap_start -= n;
+            self.gap_end -= n;
+        } else if i > self.gap_start {
+            let n = i - self.gap_start;
+            // SAFETY: the `n` elements after the gap are moved to the start of the gap
+            unsafe { ptr::copy(base.add(self.gap_end), base.add(self.gap_start), n) };
+            self.gap_start += n;
+            self.gap_end += n;
+        }
+        Ok(())
+    }
+
+    /// Moves the cursor up to `n` elements to the left and returns how far it moved.
+    pub fn move_left(&mut self, n: usize) -> usize {
+        let n = n.min(self.gap_start);
+        let _ = self.set_cursor(self.gap_start - n);
+        n
+    }
+
+    /// Moves the cursor up to `n` elements to the right and returns how far it moved.
+    pub fn move_right(&mut self, n: usize) -> usize {
+        let n = n.min(self.len() - self.gap_start);
+        let _ = self.set_cursor(self.gap_start + n);
+        n
+    }
+
+    /// Inserts `val` at the cursor and moves the cursor behind it.
+    pub fn insert(&mut self, val: T) {
+        self.reserve(1);
+
+        self.arr[self.gap_start].write(val);
+        self.gap_start += 1;
+    }
+
+    pub fn insert_slice(&mut self, vals: &[T])
+    where
+        T: Copy,
+    {
+        self.reserve(vals.len());
+
+        let base = self.base();
+        // SAFETY: the gap has room for all values, they are copied to its start
+        unsafe {
+            ptr::copy_nonoverlapping(vals.as_ptr(), base.add(self.gap_start), vals.len());
+        }
+        self.gap_start += vals.len();
+    }
+
+    /// Removes the element after the cursor.
+    pub fn delete(&mut self) -> Result<T, Error> {
+        if self.gap_end == self.capacity {
+            return Err(anyhow!("cursor at end"));
+        }
+
+        // SAFETY: the slot after the gap is initialized and becomes part of the gap,
+        // so it is never read again
+        let val = unsafe { self.arr[self.gap_end].assume_init_read() };
+        self.gap_end += 1;
+        Ok(val)
+    }
+
+    /// Removes the element before the cursor.
+    pub fn backspace(&mut self) -> Result<T, Error> {
+        if self.gap_start == 0 {
+            return Err(anyhow!("cursor at start"));
+        }
+
+        self.gap_start -= 1;
+        // SAFETY: the slot before the gap is initialized and becomes part of the gap,
+        // so it is never read again
+        Ok(unsafe { self.arr[self.gap_start].assume_init_read() })
+    }
+
+    /// Removes `n` elements after the cursor, or fewer if the end is reached.
+    pub fn delete_n(&mut self, n: usize) {
+        let n = n.min(self.capacity - self.gap_end);
+        let deleted = ptr::slice_from_raw_parts_mut(
+            // SAFETY: the pointer stays inside of `arr`
+            unsafe { self.base().add(self.gap_end) },
+            n,
+        );
+        // the gap grows first, so a panicking drop cannot cause a double drop
+        self.gap_end += n;
+        // SAFETY: the slots were initialized and are part of the gap now
+        unsafe { ptr::drop_in_place(deleted) };
+    }
+
+    pub fn clear(&mut self) {
+        let _ = self.set_cursor(0);
+        self.delete_n(self.len());
+    }
+
+    pub fn at(&self, i: usize) -> Result<&T, Error> {
+        if i >= self.len() {
+            return Err(anyhow!("index {i} out of bounds"));
+        }
+
+        let slot = if i < self.gap_start {
+            i
+        } else {
+            i + self.gap_end - self.gap_start
+        };
+        // SAFETY: slots outside of the gap are initialized
+        Ok(unsafe { self.arr[slot].assume_init_ref() })
+    }
+
+    /// Returns the elements before and after the cursor.
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        let (front, rest) = self.arr.split_at(self.gap_start);
+        let back = &rest[self.gap_end - self.gap_start..];
+        // SAFETY: slots outside of the gap are initialized
+        unsafe {
+            (
+                slice::from_raw_parts(front.as_ptr().cast(), front.len()),
+                slice::from_raw_parts(back.as_ptr().cast(), back.len()),
+            )
+        }
+    }
+
+    /// Returns the elements in `range` as the parts before and after the cursor.
+    pub fn range<R>(&self, range: R) -> Result<(&[T], &[T]), Error>
+    where
+        R: RangeBounds<usize>,
+    {
+        let start = match range.start_bound() {
+            Bound::Included(&i) => i,
+            Bound::Excluded(&i) => i + 1,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&i) => i + 1,
+            Bound::Excluded(&i) => i,
+            Bound::Unbounded => self.len(),
+        };
+        if start > end || end > self.len() {
+            return Err(anyhow!("range {start}..{end} out of bounds"));
+        }
+
+        let (front, back) = self.as_slices();
+        let n = front.len();
+        Ok((
+            &front[start.min(n)..end.min(n)],
+            &back[start.saturating_sub(n)..end.saturating_sub(n)],
+        ))
+    }
+
+    pub fn iter(&self) -> Chain<slice::Iter<'_, T>, slice::Iter<'_, T>> {
+        let (front, back) = self.as_slices();
+        front.iter().chain(back)
+    }
+}
+
+impl<T> Drop for GapBuffer<T> {
+    fn drop(&mut self) {
+        let base = self.base();
+        let front = ptr::slice_from_raw_parts_mut(base, self.gap_start);
+        // SAFETY: the pointer stays inside of `arr`
+        let back = unsafe { base.add(self.gap_end) };
+        let back = ptr::slice_from_raw_parts_mut(back, self.capacity - self.gap_end);
+        // SAFETY: both sides of the gap are initialized and dropped once
+        unsafe {
+            ptr::drop_in_place(front);
+            ptr::drop_in_place(back);
+        }
+    }
+}
+
+impl<T> Default for GapBuffer<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Clone for GapBuffer<T>
+where
+    T: Clone,
+{
+    fn clone(&self) -> Self {
+        let mut buf = Self::with_capacity_and_policy(self.len(), self.policy);
+        buf.extend(self.iter().cloned());
+        let _ = buf.set_cursor(self.gap_start);
+        buf
+    }
+}
+
+impl<T> Debug for GapBuffer<T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+/// Inserts the values at the cursor.
+impl<T> Extend<T> for GapBuffer<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+        for val in iter {
+            self.insert(val);
+        }
+    }
+}
+
+impl<T> FromIterator<T> for GapBuffer<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut buf = Self::new();
+        buf.extend(iter);
+        buf
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        let mut buf = GapBuffer::with_capacity(2);
+
+        buf.insert(1);
+        buf.insert(3);
+        buf.insert(4);
+        assert_eq!(buf.cursor(), 3);
+        assert!(buf.set_cursor(1).is_ok());
+        buf.insert(2);
+        assert!(buf.set_cursor(0).is_ok());
+        buf.insert(0);
+        assert!(buf.iter().copied().eq(0..5));
+
+        assert!(buf.at(4).is_ok_and(|i| *i == 4));
+        assert!(buf.at(5).is_err());
+        assert!(buf.set_cursor(6).is_err());
+    }
+
+    #[test]
+    fn delete_and_backspace() {
+        let mut buf: GapBuffer<i32> = (0..6).collect();
+
+        assert!(buf.delete().is_err());
+        assert!(buf.backspace().is_ok_and(|i| i == 5));
+        assert_eq!(buf.move_left(2), 2);
+        assert!(buf.delete().is_ok_and(|i| i == 3));
+        assert!(buf.backspace().is_ok_and(|i| i == 2));
+        assert_eq!(buf.move_left(10), 2);
+        assert!(buf.backspace().is_err());
+        assert_eq!(buf.move_right(1), 1);
+        buf.delete_n(10);
+        assert!(buf.iter().copied().eq([0]));
+        assert_eq!(buf.move_right(1), 0);
+    }
+
+    #[test]
+    fn ranges() {
+        let mut buf: GapBuffer<i32> = (0..10).collect();
+        assert!(buf.set_cursor(4).is_ok());
+
+        assert_eq!(
+            buf.range(..).unwrap(),
+            (&[0, 1, 2, 3][..], &[4, 5, 6, 7, 8, 9][..])
+        );
+        assert_eq!(buf.range(1..3).unwrap(), (&[1, 2][..], &[][..]));
+        assert_eq!(buf.range(2..=5).unwrap(), (&[2, 3][..], &[4, 5][..]));
+        assert_eq!(buf.range(6..).unwrap(), (&[][..], &[6, 7, 8, 9][..]));
+        assert_eq!(buf.range(4..4).unwrap(), (&[][..], &[][..]));
+        assert!(buf.range(5..11).is_err());
+        assert!(buf.range((Bound::Included(5), Bound::Excluded(4))).is_err());
+    }
+
+    #[test]
+    fn grows_around_gap() {
+        let mut buf = GapBuffer::with_capacity(4);
+        buf.insert_slice(b"held");
+        assert!(buf.set_cursor(3).is_ok());
+        buf.insert_slice(b"lo, worl");
+        assert_eq!(buf.iter().copied().collect::<Vec<_>>(), b"hello, world");
+        assert_eq!(buf.stats().reallocations, 1);
+
+        buf.shrink_to_fit();
+        assert_eq!(buf.capacity(), 12);
+        assert_eq!(buf.cursor(), 11);
+        assert_eq!(buf.iter().copied().collect::<Vec<_>>(), b"hello, world");
+    }
+
+    #[test]
+    fn non_copy_values() {
+        let mut buf = GapBuffer::new();
+        for i in 0..20 {
+            buf.insert(i.to_string());
+            buf.move_left(i % 3);
+        }
+
+        let mut cloned = buf.clone();
+        assert_eq!(cloned.cursor(), buf.cursor());
+        assert_eq!(format!("{cloned:?}"), format!("{buf:?}"));
+        cloned.clear();
+        assert!(cloned.is_empty());
+        assert_eq!(buf.len(), 20);
+    }
+
+    #[test]
+    fn drops_values() {
+        let counter = Rc::new(());
+        let mut buf = GapBuffer::with_capacity(1);
+        for _ in 0..8 {
+            buf.insert(Rc::clone(&counter));
+        }
+        buf.move_left(3);
+        assert_eq!(Rc::strong_count(&counter), 9);
+
+        drop(buf.delete());
+        drop(buf.backspace());
+        assert_eq!(Rc::strong_count(&counter), 7);
+        buf.delete_n(1);
+        assert_eq!(Rc::strong_count(&counter), 6);
+
+        drop(buf);
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+}
@@ -0,0 +1,61 @@
+/// GrowthPolicy decides how big the new allocation of a full buffer is.
+/// The result is always big enough for the elements that have to fit,
+/// even if the policy itself would grow by less.
+#[derive(Debug, Clone, Copy, Default)]
+pub enum GrowthPolicy {
+    #[default]
+    Double,
+    OneAndHalf,
+    Increment(usize),
+    /// Maps the current capacity to the next one.
+    Custom(fn(usize) -> usize),
+}
+
+impl GrowthPolicy {
+    pub(crate) fn next_capacity(&self, capacity: usize, required: usize) -> usize {
+        let next = match self {
+            Self::Double => capacity.saturating_mul(2),
+            Self::OneAndHalf => capacity.saturating_add(capacity / 2),
+            Self::Increment(n) => capacity.saturating_add(*n),
+            Self::Custom(f) => f(capacity),
+        };
+
+        next.max(required)
+    }
+}
+
+/// AllocStats counts how often a buffer moved its elements to a new allocation,
+/// both for growing and shrinking.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    pub reallocations: usize,
+    pub bytes_copied: usize,
+}
+
+impl AllocStats {
+    pub(crate) fn record<T>(&mut self, elements: usize) {
+        self.reallocations += 1;
+        self.bytes_copied += elements * std::mem::size_of::<T>();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn next_capacity() {
+        assert_eq!(GrowthPolicy::Double.next_capacity(8, 9), 16);
+        assert_eq!(GrowthPolicy::OneAndHalf.next_capacity(8, 9), 12);
+        assert_eq!(GrowthPolicy::Increment(3).next_capacity(8, 9), 11);
+        assert_eq!(GrowthPolicy::Custom(|c| c + 100).next_capacity(8, 9), 108);
+    }
+
+    #[test]
+    fn fits_required() {
+        assert_eq!(GrowthPolicy::Double.next_capacity(0, 1), 1);
+        assert_eq!(GrowthPolicy::OneAndHalf.next_capacity(1, 2), 2);
+        assert_eq!(GrowthPolicy::Increment(0).next_capacity(4, 5), 5);
+        assert_eq!(GrowthPolicy::Custom(|_| 0).next_capacity(4, 20), 20);
+    }
+}
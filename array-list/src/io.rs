@@ -0,0 +1,87 @@
+use std::io::{BufRead, Read, Result, Write};
+
+use crate::ring_buffer::RingBuffer;
+
+/// Reading takes bytes from the front, copying at most two segments per call.
+impl Read for RingBuffer<u8> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        Ok(self.pop_front_slice(buf))
+    }
+}
+
+/// Writing appends bytes at the back and grows the buffer as needed,
+/// so it never writes less than the whole input.
+impl Write for RingBuffer<u8> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        self.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// The filled buffer is the segment up to the end of the array,
+/// the wrapped segment follows once that is consumed.
+impl BufRead for RingBuffer<u8> {
+    fn fill_buf(&mut self) -> Result<&[u8]> {
+        Ok(self.as_slices().0)
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.discard_front(amt)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+
+    use super::*;
+
+    #[test]
+    fn read_write() {
+        let mut r = RingBuffer::with_capacity(8);
+
+        r.write_all(b"hello").unwrap();
+        let mut buf = [0; 3];
+        assert_eq!(r.read(&mut buf).unwrap(), 3);
+        assert_eq!(&buf, b"hel");
+
+        r.write_all(b" world").unwrap();
+        assert_eq!(r.capacity(), 8);
+        let mut s = String::new();
+        r.read_to_string(&mut s).unwrap();
+        assert_eq!(s, "lo world");
+        assert_eq!(r.read(&mut buf).unwrap(), 0);
+    }
+
+    #[test]
+    fn lines_across_edge() {
+        let mut r = RingBuffer::with_capacity(16);
+        r.write_all(b"0123456789\n").unwrap();
+        let mut line = String::new();
+        r.read_line(&mut line).unwrap();
+        assert_eq!(line, "0123456789\n");
+
+        write!(r, "first\nsecond\n").unwrap();
+        assert_eq!(r.capacity(), 16);
+        let lines: Vec<_> = r.lines().map(|l| l.unwrap()).collect();
+        assert_eq!(lines, ["first", "second"]);
+    }
+
+    #[test]
+    fn bulk_copies() {
+        let data: Vec<u8> = (0..=255).cycle().take(10_000).collect();
+        let mut r = RingBuffer::with_capacity(0);
+
+        r.write_all(&data).unwrap();
+        assert_eq!(r.stats().reallocations, 1);
+
+        let mut out = Vec::new();
+        io::copy(&mut r, &mut out).unwrap();
+        assert_eq!(out, data);
+        assert!(r.is_empty());
+    }
+}
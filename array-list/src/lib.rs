@@ -1,2 +1,24 @@
-mod array_list;
-mod ring_buffer;
+#![cfg_attr(all(test, feature = "bench"), feature(test))]
+
+pub mod array_list;
+pub mod blocking;
+pub mod bounded;
+pub mod gap_buffer;
+pub mod growth;
+mod io;
+pub mod persistent;
+pub mod ring_buffer;
+pub mod small_list;
+pub mod spsc;
+pub mod text_buffer;
+pub mod window;
+
+pub use array_list::ArrayList;
+pub use bounded::BoundedRingBuffer;
+pub use gap_buffer::GapBuffer;
+pub use growth::{AllocStats, GrowthPolicy};
+pub use persistent::PersistentVector;
+pub use ring_buffer::RingBuffer;
+pub use small_list::SmallList;
+pub use text_buffer::TextBuffer;
+pub use window::SlidingWindow;
@@ -0,0 +1,402 @@
+use std::{fmt::Debug, rc::Rc};
+
+use anyhow::{anyhow, Error};
+
+const BITS: usize = 5;
+const WIDTH: usize = 1 << BITS;
+const MASK: usize = WIDTH - 1;
+
+/// Node of the trie. Leaves hold up to `WIDTH` values, branches up to `WIDTH` children.
+/// Nodes are never changed once they are shared, updates copy the path to the root instead.
+enum Node<T> {
+    Branch(Vec<Rc<Node<T>>>),
+    Leaf(Vec<T>),
+}
+
+impl<T> Node<T> {
+    fn children(&self) -> &[Rc<Node<T>>] {
+        match self {
+            Node::Branch(children) => children,
+            Node::Leaf(_) => unreachable!("only branches are above level 0"),
+        }
+    }
+
+    fn values(&self) -> &[T] {
+        match self {
+            Node::Leaf(values) => values,
+            Node::Branch(_) => unreachable!("only leaves are at level 0"),
+        }
+    }
+}
+
+/// PersistentVector is an immutable vector.
+/// `push`, `set` and `pop` return a new version that shares all but
+/// the path to the changed element with the old one, and cloning is O(1).
+/// The values are stored in a trie with 32 children per node,
+/// the `shift` is the number of index bits below the root.
+pub struct PersistentVector<T> {
+    len: usize,
+    shift: usize,
+    root: Rc<Node<T>>,
+}
+
+impl<T> PersistentVector<T> {
+    pub fn new() -> Self {
+        Self {
+            len: 0,
+            shift: 0,
+            root: Rc::new(Node::Leaf(Vec::new())),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the leaf that holds index `i`.
+    fn leaf(&self, i: usize) -> &[T] {
+        let mut node = &self.root;
+        let mut level = self.shift;
+        while level > 0 {
+            node = &node.children()[(i >> level) & MASK];
+            level -= BITS;
+        }
+        node.values()
+    }
+
+    pub fn at(&self, i: usize) -> Result<&T, Error> {
+        if i >= self.len {
+            return Err(anyhow!("index {i} out of bounds"));
+        }
+
+        Ok(&self.leaf(i)[i & MASK])
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            vec: self,
+            i: 0,
+            leaf: &[],
+        }
+    }
+
+    /// Returns true if both versions share the same trie.
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.root, &other.root)
+    }
+}
+
+fn new_path<T>(level: usize, val: T) -> Rc<Node<T>> {
+    if level == 0 {
+        Rc::new(Node::Leaf(vec![val]))
+    } else {
+        Rc::new(Node::Branch(vec![new_path(level - BITS, val)]))
+    }
+}
+
+fn push_in<T>(node: &Node<T>, level: usize, i: usize, val: T) -> Rc<Node<T>>
+where
+    T: Clone,
+{
+    if level == 0 {
+        let mut values = node.values().to_vec();
+        values.push(val);
+        return Rc::new(Node::Leaf(values));
+    }
+
+    let mut children = node.children().to_vec();
+    let idx = (i >> level) & MASK;
+    if idx < children.len() {
+        children[idx] = push_in(&children[idx], level - BITS, i, val);
+    } else {
+        children.push(new_path(level - BITS, val));
+    }
+    Rc::new(Node::Branch(children))
+}
+
+fn set_in<T>(node: &Node<T>, level: usize, i: usize, val: T) -> Rc<Node<T>>
+where
+    T: Clone,
+{
+    if level == 0 {
+        let mut values = node.values().to_vec();
+        values[i & MASK] = val;
+        return Rc::new(Node::Leaf(values));
+    }
+
+    let mut children = node.children().to_vec();
+    let idx = (i >> level) & MASK;
+    children[idx] = set_in(&children[idx], level - BITS, i, val);
+    Rc::new(Node::Branch(children))
+}
+
+/// Removes the value at `i`, which has to be the last one.
+/// Returns None if the node is empty afterwards.
+fn pop_in<T>(node: &Node<T>, level: usize, i: usize) -> Option<Rc<Node<T>>>
+where
+    T: Clone,
+{
+    if level == 0 {
+        let values = &node.values()[..i & MASK];
+        return (!values.is_empty()).then(|| Rc::new(Node::Leaf(values.to_vec())));
+    }
+
+    let mut children = node.children().to_vec();
+    let idx = (i >> level) & MASK;
+    match pop_in(&children[idx], level - BITS, i) {
+        Some(child) => children[idx] = child,
+        None => {
+            children.pop();
+        }
+    }
+    (!children.is_empty()).then(|| Rc::new(Node::Branch(children)))
+}
+
+impl<T> PersistentVector<T>
+where
+    T: Clone,
+{
+    pub fn push(&self, val: T) -> Self {
+        // the trie is full, so it gets a new root one level higher
+        if self.len == 1 << (self.shift + BITS) {
+            let root = Node::Branch(vec![Rc::clone(&self.root), new_path(self.shift, val)]);
+            return Self {
+                len: self.len + 1,
+                shift: self.shift + BITS,
+                root: Rc::new(root),
+            };
+        }
+
+        Self {
+            len: self.len + 1,
+            shift: self.shift,
+            root: push_in(&self.root, self.shift, self.len, val),
+        }
+    }
+
+    pub fn set(&self, i: usize, val: T) -> Result<Self, Error> {
+        if i >= self.len {
+            return Err(anyhow!("index {i} out of bounds"));
+        }
+
+        Ok(Self {
+            len: self.len,
+            shift: self.shift,
+            root: set_in(&self.root, self.shift, i, val),
+        })
+    }
+
+    /// Returns the new version without the last value, together with that value.
+    pub fn pop(&self) -> Result<(Self, T), Error> {
+        if self.len == 0 {
+            return Err(anyhow!("empty vector"));
+        }
+
+        let i = self.len - 1;
+        let val = self.leaf(i)[i & MASK].clone();
+        let Some(mut root) = pop_in(&self.root, self.shift, i) else {
+            return Ok((Self::new(), val));
+        };
+
+        // a root with a single child is not needed anymore
+        let mut shift = self.shift;
+        while shift > 0 && root.children().len() == 1 {
+            root = Rc::clone(&root.children()[0]);
+            shift -= BITS;
+        }
+
+        Ok((
+            Self {
+                len: i,
+                shift,
+                root,
+            },
+            val,
+        ))
+    }
+}
+
+impl<T> Clone for PersistentVector<T> {
+    fn clone(&self) -> Self {
+        Self {
+            len: self.len,
+            shift: self.shift,
+            root: Rc::clone(&self.root),
+        }
+    }
+}
+
+impl<T> Default for PersistentVector<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Debug for PersistentVector<T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T> PartialEq for PersistentVector<T>
+where
+    T: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && (self.ptr_eq(other) || self.iter().eq(other.iter()))
+    }
+}
+
+impl<T> Eq for PersistentVector<T> where T: Eq {}
+
+impl<T> FromIterator<T> for PersistentVector<T>
+where
+    T: Clone,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        iter.into_iter().fold(Self::new(), |v, val| v.push(val))
+    }
+}
+
+/// Iter walks the values leaf by leaf.
+pub struct Iter<'a, T> {
+    vec: &'a PersistentVector<T>,
+    i: usize,
+    leaf: &'a [T],
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        if self.i >= self.vec.len {
+            return None;
+        }
+        if self.leaf.is_empty() {
+            self.leaf = self.vec.leaf(self.i);
+        }
+
+        let (val, rest) = self.leaf.split_first()?;
+        self.leaf = rest;
+        self.i += 1;
+        Some(val)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.vec.len - self.i;
+        (len, Some(len))
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+impl<'a, T> IntoIterator for &'a PersistentVector<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        let v = PersistentVector::new();
+        let v = v.push(0).push(1).push(2);
+
+        assert_eq!(v.len(), 3);
+        assert!(v.at(0).is_ok_and(|i| *i == 0));
+        assert!(v.at(2).is_ok_and(|i| *i == 2));
+        assert!(v.at(3).is_err());
+
+        let (v, last) = v.pop().unwrap();
+        assert_eq!(last, 2);
+        let (v, _) = v.pop().unwrap();
+        let (v, first) = v.pop().unwrap();
+        assert_eq!(first, 0);
+        assert!(v.pop().is_err());
+    }
+
+    #[test]
+    fn old_versions_stay_intact() {
+        let v1: PersistentVector<String> = (0..100).map(|i| i.to_string()).collect();
+        let v2 = v1.push("100".to_string());
+        let v3 = v1.set(50, "fifty".to_string()).unwrap();
+        let (v4, popped) = v1.pop().unwrap();
+
+        assert_eq!(v1.len(), 100);
+        assert!(v1.at(50).is_ok_and(|s| s == "50"));
+        assert!(v1.at(100).is_err());
+        assert!(v2.at(100).is_ok_and(|s| s == "100"));
+        assert!(v3.at(50).is_ok_and(|s| s == "fifty"));
+        assert!(v2.at(50).is_ok_and(|s| s == "50"));
+        assert_eq!(popped, "99");
+        assert_eq!(v4.len(), 99);
+        assert!(v1.set(100, String::new()).is_err());
+    }
+
+    #[test]
+    fn grows_and_shrinks_levels() {
+        let n = WIDTH * WIDTH + 3;
+        let mut versions = vec![PersistentVector::new()];
+        for i in 0..n {
+            let next = versions[i].push(i);
+            versions.push(next);
+        }
+
+        let full = &versions[n];
+        assert_eq!(full.shift, 2 * BITS);
+        assert!(full.iter().copied().eq(0..n));
+        for (len, v) in versions.iter().enumerate().step_by(97) {
+            assert!(v.iter().copied().eq(0..len));
+        }
+
+        let mut v = full.clone();
+        for i in (0..n).rev() {
+            let (next, val) = v.pop().unwrap();
+            assert_eq!(val, i);
+            v = next;
+            if i == WIDTH * WIDTH {
+                assert_eq!(v.shift, BITS);
+                assert_eq!(v, versions[i]);
+            }
+            if i == WIDTH {
+                assert_eq!(v.shift, 0);
+                assert_eq!(v, versions[i]);
+            }
+        }
+        assert_eq!(v.shift, 0);
+        assert!(v.is_empty());
+        assert_eq!(full.len(), n);
+    }
+
+    #[test]
+    fn shares_structure() {
+        let v1: PersistentVector<i32> = (0..1000).collect();
+        let v2 = v1.clone();
+        assert!(v1.ptr_eq(&v2));
+
+        let v3 = v1.set(999, -1).unwrap();
+        assert!(!v1.ptr_eq(&v3));
+        // only the path to the last leaf was copied
+        let shared = v1
+            .root
+            .children()
+            .iter()
+            .zip(v3.root.children())
+            .filter(|(a, b)| Rc::ptr_eq(a, b))
+            .count();
+        assert_eq!(shared, v1.root.children().len() - 1);
+    }
+}
@@ -1,100 +1,491 @@
+use std::{
+    fmt::Debug,
+    mem::{self, MaybeUninit},
+    ops::{Index, IndexMut},
+    ptr, slice,
+};
+
 use anyhow::{anyhow, Error};
 
-#[allow(dead_code)]
-#[derive(Debug)]
-struct RingBuffer<T> {
-    start: usize, // including
-    end: usize,   // excluding
+use crate::growth::{AllocStats, GrowthPolicy};
+
+/// RingBuffer is a growable double-ended queue.
+/// The elements are stored in `arr` from `start` on and wrap around at the end,
+/// all other slots are uninitialized.
+pub struct RingBuffer<T> {
+    start: usize,
+    len: usize,
     capacity: usize,
-    arr: Box<[T]>,
+    arr: Box<[MaybeUninit<T>]>,
+    policy: GrowthPolicy,
+    stats: AllocStats,
 }
 
 const INITIAL_CAP: usize = 5;
 
-#[allow(dead_code)]
-impl<T> RingBuffer<T>
-where
-    T: Copy + Default + std::fmt::Debug,
-{
-    fn new() -> Self {
+impl<T> RingBuffer<T> {
+    pub fn new() -> Self {
+        Self::with_capacity(INITIAL_CAP)
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::with_capacity_and_policy(capacity, GrowthPolicy::default())
+    }
+
+    pub fn with_policy(policy: GrowthPolicy) -> Self {
+        Self::with_capacity_and_policy(INITIAL_CAP, policy)
+    }
+
+    pub fn with_capacity_and_policy(capacity: usize, policy: GrowthPolicy) -> Self {
         Self {
             start: 0,
-            end: 0,
-            capacity: INITIAL_CAP,
-            arr: vec![T::default(); INITIAL_CAP].into_boxed_slice(),
+            len: 0,
+            capacity,
+            arr: Box::new_uninit_slice(capacity),
+            policy,
+            stats: AllocStats::default(),
         }
     }
 
-    fn len(&self) -> usize {
-        if self.start > self.end {
-            self.capacity - self.start + self.end
-        } else {
-            self.end - self.start
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn stats(&self) -> AllocStats {
+        self.stats
+    }
+
+    /// Makes sure that `additional` more elements fit without reallocating.
+    pub fn reserve(&mut self, additional: usize) {
+        let required = self.len + additional;
+        if required > self.capacity {
+            self.reallocate(self.policy.next_capacity(self.capacity, required));
         }
     }
 
-    fn push(&mut self, val: T) {
-        if self.start == 0 && self.end == self.capacity
-            || self.start > 0 && self.end % self.capacity == self.start - 1
-        {
-            let new_capacity = self.capacity * 2;
-            let mut new_array = vec![T::default(); new_capacity].into_boxed_slice();
-            if self.start <= self.end {
-                let new_end = self.end - self.start;
-                new_array[..new_end].copy_from_slice(&self.arr[self.start..self.end]);
-                self.end = new_end;
+    fn reallocate(&mut self, new_capacity: usize) {
+        let mut new_array = Box::new_uninit_slice(new_capacity);
+        // SAFETY: both segments are initialized and get moved bitwise to the front
+        // of the new array, the old array is treated as uninitialized afterwards
+        unsafe {
+            let src = self.arr.as_ptr();
+            let dst = new_array.as_mut_ptr();
+            if self.start + self.len <= self.capacity {
+                ptr::copy_nonoverlapping(src.add(self.start), dst, self.len);
             } else {
                 let num_elem_right = self.capacity - self.start;
-                new_array[..num_elem_right].copy_from_slice(&self.arr[self.start..self.capacity]);
-                new_array[num_elem_right..num_elem_right + self.end]
-                    .copy_from_slice(&self.arr[..self.end]);
-                self.end += num_elem_right;
+                let num_elem_left = self.len - num_elem_right;
+                ptr::copy_nonoverlapping(src.add(self.start), dst, num_elem_right);
+                ptr::copy_nonoverlapping(src, dst.add(num_elem_right), num_elem_left);
             }
-            self.start = 0;
-            self.arr = new_array;
-            self.capacity = new_capacity;
         }
+        self.start = 0;
+        self.arr = new_array;
+        self.capacity = new_capacity;
+        self.stats.record::<T>(self.len);
+    }
+
+    /// Maps the logical index `i` to its slot in `arr`.
+    /// Must only be called with a non-zero capacity.
+    fn index(&self, i: usize) -> usize {
+        (self.start + i) % self.capacity
+    }
+
+    pub fn push(&mut self, val: T) {
+        self.push_back(val)
+    }
+
+    pub fn push_back(&mut self, val: T) {
+        self.reserve(1);
+
+        let i = self.index(self.len);
+        self.arr[i].write(val);
+        self.len += 1;
+    }
+
+    pub fn push_front(&mut self, val: T) {
+        self.reserve(1);
+
+        self.start = self.index(self.capacity - 1);
+        self.arr[self.start].write(val);
+        self.len += 1;
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+
+        self.len -= 1;
+        let i = self.index(self.len);
+        // SAFETY: the slot held the last element and is now outside of the buffer
+        Some(unsafe { self.arr[i].assume_init_read() })
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+
+        let prev = self.start;
+        self.start = self.index(1);
+        self.len -= 1;
+        // SAFETY: the slot held the first element and is now outside of the buffer
+        Some(unsafe { self.arr[prev].assume_init_read() })
+    }
+
+    pub fn at(&self, i: usize) -> Result<&T, Error> {
+        self.get(i)
+            .ok_or_else(|| anyhow!("index {i} is out of bounds"))
+    }
+
+    pub fn pop(&mut self) -> Result<T, Error> {
+        self.pop_back().ok_or_else(|| anyhow!("buffer empty"))
+    }
+
+    pub fn deque(&mut self) -> Result<T, Error> {
+        self.pop_front().ok_or_else(|| anyhow!("buffer empty"))
+    }
+
+    pub fn get(&self, i: usize) -> Option<&T> {
+        if i >= self.len {
+            return None;
+        }
+
+        // SAFETY: logical indices below `len` are initialized
+        Some(unsafe { self.arr[self.index(i)].assume_init_ref() })
+    }
+
+    pub fn get_mut(&mut self, i: usize) -> Option<&mut T> {
+        if i >= self.len {
+            return None;
+        }
+
+        let i = self.index(i);
+        // SAFETY: logical indices below `len` are initialized
+        Some(unsafe { self.arr[i].assume_init_mut() })
+    }
+
+    pub fn front(&self) -> Option<&T> {
+        self.get(0)
+    }
 
-        self.arr[self.end % self.capacity] = val;
-        self.end = self.end % self.capacity + 1;
+    pub fn back(&self) -> Option<&T> {
+        self.get(self.len.wrapping_sub(1))
     }
 
-    fn at(&self, i: usize) -> Result<T, Error> {
-        if self.start < self.end && i >= self.end - self.start
-            || i >= self.capacity - self.start + self.end
-        {
-            return Err(anyhow!("index {i} is out of bounds"));
+    pub fn clear(&mut self) {
+        while self.pop_back().is_some() {}
+        self.start = 0;
+    }
+
+    /// Removes up to `n` elements from the front.
+    pub fn discard_front(&mut self, n: usize) {
+        let n = n.min(self.len);
+        if mem::needs_drop::<T>() {
+            for _ in 0..n {
+                self.pop_front();
+            }
+        } else if n > 0 {
+            self.start = self.index(n);
+            self.len -= n;
+        }
+    }
+
+    /// Appends all of `vals` at the back, copying at most two segments:
+    /// up to the end of the array and the rest wrapped around to its front.
+    pub fn extend_from_slice(&mut self, vals: &[T])
+    where
+        T: Copy,
+    {
+        if vals.is_empty() {
+            return;
+        }
+        self.reserve(vals.len());
+
+        let end = self.index(self.len);
+        let num_elem_right = vals.len().min(self.capacity - end);
+        let num_elem_left = vals.len() - num_elem_right;
+        let src = vals.as_ptr();
+        let dst = self.arr.as_mut_ptr().cast::<T>();
+        // SAFETY: the reserved space is free and starts at `end`, wrapping around to the front
+        unsafe {
+            ptr::copy_nonoverlapping(src, dst.add(end), num_elem_right);
+            ptr::copy_nonoverlapping(src.add(num_elem_right), dst, num_elem_left);
         }
+        self.len += vals.len();
+    }
 
-        Ok(self.arr[self.start + i % self.capacity])
+    /// Moves as many elements from the front into `out` as fit
+    /// and returns how many were moved.
+    pub fn pop_front_slice(&mut self, out: &mut [T]) -> usize
+    where
+        T: Copy,
+    {
+        let (right, left) = self.as_slices();
+        let num_elem_right = right.len().min(out.len());
+        let num_elem_left = left.len().min(out.len() - num_elem_right);
+        out[..num_elem_right].copy_from_slice(&right[..num_elem_right]);
+        out[num_elem_right..num_elem_right + num_elem_left].copy_from_slice(&left[..num_elem_left]);
+
+        let n = num_elem_right + num_elem_left;
+        self.discard_front(n);
+        n
     }
 
-    fn pop(&mut self) -> Result<T, Error> {
-        if self.len() == 0 {
-            return Err(anyhow!("buffer empty"));
+    /// Returns the elements as the segment from `start` to the end of the array
+    /// and the segment that wrapped around to the front of it.
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        let (right, left) = self.segments();
+        let ptr = self.arr.as_ptr().cast::<T>();
+        // SAFETY: both segments are initialized
+        unsafe {
+            (
+                slice::from_raw_parts(ptr.add(self.start), right),
+                slice::from_raw_parts(ptr, left),
+            )
         }
+    }
+
+    pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        let (right, left) = self.segments();
+        let ptr = self.arr.as_mut_ptr().cast::<T>();
+        // SAFETY: both segments are initialized and don't overlap
+        unsafe {
+            (
+                slice::from_raw_parts_mut(ptr.add(self.start), right),
+                slice::from_raw_parts_mut(ptr, left),
+            )
+        }
+    }
 
-        if self.end > 0 {
-            self.end -= 1;
+    fn segments(&self) -> (usize, usize) {
+        if self.start + self.len <= self.capacity {
+            (self.len, 0)
         } else {
-            self.end = self.capacity;
+            let num_elem_right = self.capacity - self.start;
+            (num_elem_right, self.len - num_elem_right)
         }
-        Ok(self.arr[self.end])
     }
 
-    fn deque(&mut self) -> Result<T, Error> {
-        if self.len() == 0 {
-            return Err(anyhow!("buffer empty"));
+    /// Rotates the elements in place, so that they start at the front of the array.
+    pub fn make_contiguous(&mut self) -> &mut [T] {
+        self.arr.rotate_left(self.start);
+        self.start = 0;
+        self.as_mut_slices().0
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        let (right, left) = self.as_slices();
+        Iter {
+            right: right.iter(),
+            left: left.iter(),
         }
+    }
 
-        let prev = self.start;
-        self.start = self.start + 1 % self.capacity;
-        Ok(self.arr[prev])
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        let (right, left) = self.as_mut_slices();
+        IterMut {
+            right: right.iter_mut(),
+            left: left.iter_mut(),
+        }
+    }
+}
+
+impl<T> Drop for RingBuffer<T> {
+    fn drop(&mut self) {
+        let (right, left) = self.as_mut_slices();
+        // SAFETY: both segments are initialized and never used again
+        unsafe {
+            ptr::drop_in_place(right);
+            ptr::drop_in_place(left);
+        }
+    }
+}
+
+impl<T> Default for RingBuffer<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Clone for RingBuffer<T>
+where
+    T: Clone,
+{
+    fn clone(&self) -> Self {
+        let mut r = Self::with_capacity_and_policy(self.len, self.policy);
+        r.extend(self.iter().cloned());
+        r
+    }
+}
+
+impl<T> Debug for RingBuffer<T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T> Index<usize> for RingBuffer<T> {
+    type Output = T;
+
+    fn index(&self, i: usize) -> &T {
+        self.get(i).expect("index out of bounds")
+    }
+}
+
+impl<T> IndexMut<usize> for RingBuffer<T> {
+    fn index_mut(&mut self, i: usize) -> &mut T {
+        self.get_mut(i).expect("index out of bounds")
+    }
+}
+
+impl<T> Extend<T> for RingBuffer<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+
+        for val in iter {
+            self.push_back(val);
+        }
+    }
+}
+
+impl<T> FromIterator<T> for RingBuffer<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut r = Self::new();
+        r.extend(iter);
+        r
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for RingBuffer<T> {
+    fn from(arr: [T; N]) -> Self {
+        let mut r = Self::with_capacity(N);
+        r.extend(arr);
+        r
+    }
+}
+
+/// Iter walks the elements from front to back, across the end of the array.
+pub struct Iter<'a, T> {
+    right: slice::Iter<'a, T>,
+    left: slice::Iter<'a, T>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        self.right.next().or_else(|| self.left.next())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.right.len() + self.left.len();
+        (len, Some(len))
+    }
+}
+
+impl<T> DoubleEndedIterator for Iter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.left.next_back().or_else(|| self.right.next_back())
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+pub struct IterMut<'a, T> {
+    right: slice::IterMut<'a, T>,
+    left: slice::IterMut<'a, T>,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<&'a mut T> {
+        self.right.next().or_else(|| self.left.next())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.right.len() + self.left.len();
+        (len, Some(len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IterMut<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.left.next_back().or_else(|| self.right.next_back())
+    }
+}
+
+impl<T> ExactSizeIterator for IterMut<'_, T> {}
+
+pub struct IntoIter<T> {
+    buf: RingBuffer<T>,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.buf.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.buf.len, Some(self.buf.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<T> {
+        self.buf.pop_back()
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> IntoIterator for RingBuffer<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter { buf: self }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a RingBuffer<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut RingBuffer<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> IterMut<'a, T> {
+        self.iter_mut()
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::rc::Rc;
+
     use super::*;
 
     #[test]
@@ -106,10 +497,10 @@ mod tests {
         r.push(2);
         r.push(3);
 
-        assert!(r.at(0).is_ok_and(|i| i == 0));
-        assert!(r.at(1).is_ok_and(|i| i == 1));
-        assert!(r.at(2).is_ok_and(|i| i == 2));
-        assert!(r.at(3).is_ok_and(|i| i == 3));
+        assert!(r.at(0).is_ok_and(|i| *i == 0));
+        assert!(r.at(1).is_ok_and(|i| *i == 1));
+        assert!(r.at(2).is_ok_and(|i| *i == 2));
+        assert!(r.at(3).is_ok_and(|i| *i == 3));
         assert!(r.at(4).is_err());
 
         assert!(r.pop().is_ok_and(|i| i == 3));
@@ -167,4 +558,187 @@ mod tests {
             r.push(i);
         }
     }
+
+    #[test]
+    fn growth_policy() {
+        let mut r = RingBuffer::with_capacity_and_policy(3, GrowthPolicy::Increment(2));
+
+        for i in 0..3 {
+            r.push(i);
+        }
+        assert!(r.deque().is_ok_and(|i| i == 0));
+        r.push(3);
+        r.push(4);
+        assert_eq!(r.capacity(), 5);
+        for i in 1..5 {
+            assert!(r.deque().is_ok_and(|j| j == i));
+        }
+    }
+
+    #[test]
+    fn stats() {
+        let mut r = RingBuffer::<u32>::with_capacity(4);
+        r.reserve(4);
+        assert_eq!(r.stats(), AllocStats::default());
+
+        for i in 0..4 {
+            r.push(i);
+        }
+        assert!(r.deque().is_ok());
+        r.push(4);
+        r.push(5);
+        assert_eq!(r.capacity(), 8);
+        assert_eq!(r.stats().reallocations, 1);
+        assert_eq!(r.stats().bytes_copied, 4 * 4);
+        for i in 1..6 {
+            assert!(r.deque().is_ok_and(|j| j == i));
+        }
+    }
+
+    #[test]
+    fn push_front() {
+        let mut r = RingBuffer::new();
+
+        r.push_front(1);
+        r.push_front(0);
+        r.push_back(2);
+        for i in 3..12 {
+            r.push_back(i);
+            r.push_front(-i);
+        }
+        assert_eq!(r.len(), 21);
+        assert_eq!(r.front(), Some(&-11));
+        assert_eq!(r.back(), Some(&11));
+        assert_eq!(r.pop_front(), Some(-11));
+        assert_eq!(r.pop_back(), Some(11));
+        assert_eq!(r.get(8), Some(&0));
+        assert_eq!(r.get(19), None);
+    }
+
+    #[test]
+    fn pop_empty() {
+        let mut r = RingBuffer::<i32>::with_capacity(0);
+
+        assert_eq!(r.pop_front(), None);
+        assert_eq!(r.pop_back(), None);
+        assert_eq!(r.front(), None);
+        assert_eq!(r.back(), None);
+        r.push_front(0);
+        assert_eq!(r.pop_back(), Some(0));
+    }
+
+    #[test]
+    fn get_mut() {
+        let mut r: RingBuffer<i32> = (0..4).collect();
+
+        r.push_front(-1);
+        if let Some(v) = r.get_mut(0) {
+            *v = 10;
+        }
+        r[1] += 5;
+        assert_eq!(r.iter().copied().collect::<Vec<_>>(), [10, 5, 1, 2, 3]);
+        assert!(r.get_mut(5).is_none());
+    }
+
+    fn wrapped() -> RingBuffer<i32> {
+        let mut r = RingBuffer::with_capacity(8);
+        for i in 0..6 {
+            r.push_back(i);
+        }
+        for _ in 0..4 {
+            r.pop_front();
+        }
+        for i in 6..12 {
+            r.push_back(i);
+        }
+        r
+    }
+
+    #[test]
+    fn iter_across_edge() {
+        let mut r = wrapped();
+
+        assert_eq!(r.capacity(), 8);
+        assert_eq!(r.iter().len(), 8);
+        assert_eq!(
+            r.iter().copied().collect::<Vec<_>>(),
+            [4, 5, 6, 7, 8, 9, 10, 11]
+        );
+        assert_eq!(
+            r.iter().rev().copied().collect::<Vec<_>>(),
+            [11, 10, 9, 8, 7, 6, 5, 4]
+        );
+
+        for v in r.iter_mut() {
+            *v *= 2;
+        }
+        let mut it = r.into_iter();
+        assert_eq!(it.next(), Some(8));
+        assert_eq!(it.next_back(), Some(22));
+        assert_eq!(it.collect::<Vec<_>>(), [10, 12, 14, 16, 18, 20]);
+    }
+
+    #[test]
+    fn slices() {
+        let mut r = wrapped();
+
+        assert_eq!(r.as_slices(), (&[4, 5, 6, 7][..], &[8, 9, 10, 11][..]));
+        assert_eq!(r.make_contiguous(), [4, 5, 6, 7, 8, 9, 10, 11]);
+        assert_eq!(r.as_slices(), (&[4, 5, 6, 7, 8, 9, 10, 11][..], &[][..]));
+        assert_eq!(r.pop_front(), Some(4));
+        assert_eq!(r.capacity(), 8);
+    }
+
+    #[test]
+    fn slice_copies() {
+        let mut r = RingBuffer::with_capacity(8);
+        r.extend_from_slice(&[0, 1, 2, 3, 4, 5]);
+
+        let mut out = [0; 4];
+        assert_eq!(r.pop_front_slice(&mut out), 4);
+        assert_eq!(out, [0, 1, 2, 3]);
+
+        r.extend_from_slice(&[6, 7, 8, 9, 10]);
+        assert_eq!(r.as_slices(), (&[4, 5, 6, 7][..], &[8, 9, 10][..]));
+        r.extend_from_slice(&[11, 12]);
+        assert_eq!(r.capacity(), 16);
+        assert_eq!(r.pop_front_slice(&mut out), 4);
+        assert_eq!(out, [4, 5, 6, 7]);
+        assert_eq!(r.pop_front_slice(&mut out), 4);
+        assert_eq!(out, [8, 9, 10, 11]);
+        assert_eq!(r.pop_front_slice(&mut out), 1);
+        assert_eq!(r.pop_front_slice(&mut out), 0);
+        assert_eq!(out, [12, 9, 10, 11]);
+    }
+
+    #[test]
+    fn discard_front() {
+        let mut r: RingBuffer<String> = (0..5).map(|i| i.to_string()).collect();
+
+        r.discard_front(2);
+        assert_eq!(r.front().map(String::as_str), Some("2"));
+        r.discard_front(10);
+        assert!(r.is_empty());
+    }
+
+    #[test]
+    fn drops_values() {
+        let counter = Rc::new(());
+        let mut r = RingBuffer::with_capacity(4);
+        for _ in 0..3 {
+            r.push_back(Rc::clone(&counter));
+        }
+        r.pop_front();
+        for _ in 0..4 {
+            r.push_back(Rc::clone(&counter));
+        }
+        assert_eq!(Rc::strong_count(&counter), 7);
+
+        let mut it = r.clone().into_iter();
+        it.next();
+        assert_eq!(Rc::strong_count(&counter), 12);
+        drop(it);
+        drop(r);
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
 }
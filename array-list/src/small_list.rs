@@ -0,0 +1,709 @@
+use std::{
+    fmt::Debug,
+    mem::{self, MaybeUninit},
+    ops::{Bound, Deref, DerefMut, Index, IndexMut, RangeBounds},
+    ptr,
+    slice::{self, SliceIndex},
+};
+
+use anyhow::{anyhow, Error};
+
+use crate::array_list::{self, ArrayList};
+
+/// SmallList is an ArrayList that keeps up to `N` elements inline
+/// and only moves them to the heap once it grows past `N`.
+pub struct SmallList<T, const N: usize> {
+    storage: Storage<T, N>,
+}
+
+enum Storage<T, const N: usize> {
+    /// Only the first `len` slots of `arr` are initialized.
+    Inline {
+        len: usize,
+        arr: [MaybeUninit<T>; N],
+    },
+    Heap(ArrayList<T>),
+}
+
+impl<T, const N: usize> SmallList<T, N> {
+    pub fn new() -> Self {
+        Self {
+            storage: Storage::Inline {
+                len: 0,
+                arr: [const { MaybeUninit::uninit() }; N],
+            },
+        }
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        let mut l = Self::new();
+        l.reserve(capacity);
+        l
+    }
+
+    pub fn is_inline(&self) -> bool {
+        matches!(self.storage, Storage::Inline { .. })
+    }
+
+    pub fn len(&self) -> usize {
+        match &self.storage {
+            Storage::Inline { len, .. } => *len,
+            Storage::Heap(a) => a.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn capacity(&self) -> usize {
+        match &self.storage {
+            Storage::Inline { .. } => N,
+            Storage::Heap(a) => a.capacity(),
+        }
+    }
+
+    /// Moves the inline elements into a heap allocation with room for `capacity` elements.
+    fn spill(&mut self, capacity: usize) {
+        let Storage::Inline { len, arr } = &mut self.storage else {
+            return;
+        };
+
+        let mut heap = ArrayList::with_capacity(capacity);
+        let n = mem::take(len);
+        // SAFETY: the first `n` slots are initialized, `len` is already reset
+        // so they are only moved once
+        heap.extend(
+            arr[..n]
+                .iter()
+                .map(|slot| unsafe { slot.assume_init_read() }),
+        );
+        self.storage = Storage::Heap(heap);
+    }
+
+    /// Makes sure that `additional` more elements fit without reallocating.
+    pub fn reserve(&mut self, additional: usize) {
+        let required = self.len() + additional;
+        match &mut self.storage {
+            Storage::Inline { .. } if required > N => self.spill(required.max(N * 2)),
+            Storage::Inline { .. } => (),
+            Storage::Heap(a) => a.reserve(additional),
+        }
+    }
+
+    /// Moves the elements back inline if they fit, otherwise shrinks the heap allocation.
+    pub fn shrink_to_fit(&mut self) {
+        let Storage::Heap(heap) = &mut self.storage else {
+            return;
+        };
+        if heap.len() > N {
+            heap.shrink_to_fit();
+            return;
+        }
+
+        let mut arr = [const { MaybeUninit::uninit() }; N];
+        let mut len = 0;
+        for val in heap.drain(..).expect("full range is in bounds") {
+            arr[len].write(val);
+            len += 1;
+        }
+        self.storage = Storage::Inline { len, arr };
+    }
+
+    pub fn push(&mut self, val: T) {
+        match &mut self.storage {
+            Storage::Inline { len, arr } if *len < N => {
+                arr[*len].write(val);
+                *len += 1;
+            }
+            Storage::Inline { .. } => {
+                self.spill(N * 2);
+                self.push(val);
+            }
+            Storage::Heap(a) => a.push(val),
+        }
+    }
+
+    pub fn at(&self, i: usize) -> Result<&T, Error> {
+        self.get(i)
+            .ok_or_else(|| anyhow!("index {i} out of bounds"))
+    }
+
+    pub fn pop(&mut self) -> Result<T, Error> {
+        match &mut self.storage {
+            Storage::Inline { len: 0, .. } => Err(anyhow!("empty list")),
+            Storage::Inline { len, arr } => {
+                *len -= 1;
+                // SAFETY: the slot was initialized and is now outside of `len`
+                Ok(unsafe { arr[*len].assume_init_read() })
+            }
+            Storage::Heap(a) => a.pop(),
+        }
+    }
+
+    pub fn insert(&mut self, i: usize, val: T) -> Result<(), Error> {
+        if i > self.len() {
+            return Err(anyhow!("index {i} out of bounds"));
+        }
+
+        self.push(val);
+        self[i..].rotate_right(1);
+        Ok(())
+    }
+
+    pub fn remove(&mut self, i: usize) -> Result<T, Error> {
+        if i >= self.len() {
+            return Err(anyhow!("index {i} out of bounds"));
+        }
+
+        match &mut self.storage {
+            Storage::Inline { .. } => {
+                self[i..].rotate_left(1);
+                self.pop()
+            }
+            Storage::Heap(a) => a.remove(i),
+        }
+    }
+
+    /// Removes the element at `i` and fills the hole with the last element.
+    /// This does not preserve the order, but is O(1).
+    pub fn swap_remove(&mut self, i: usize) -> Result<T, Error> {
+        if i >= self.len() {
+            return Err(anyhow!("index {i} out of bounds"));
+        }
+
+        let last = self.len() - 1;
+        self.swap(i, last);
+        self.pop()
+    }
+
+    pub fn truncate(&mut self, new_len: usize) {
+        match &mut self.storage {
+            Storage::Inline { len, arr } => {
+                if new_len >= *len {
+                    return;
+                }
+                let tail = ptr::slice_from_raw_parts_mut(
+                    arr[new_len..].as_mut_ptr().cast::<T>(),
+                    *len - new_len,
+                );
+                // shorten first, so a panicking drop leaks the rest instead of dropping twice
+                *len = new_len;
+                // SAFETY: the tail is initialized and no longer part of the list
+                unsafe { ptr::drop_in_place(tail) };
+            }
+            Storage::Heap(a) => a.truncate(new_len),
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    /// Keeps only the elements for which `keep` returns true, in their original order.
+    pub fn retain<F>(&mut self, mut keep: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let mut kept = 0;
+        for i in 0..self.len() {
+            if keep(&self[i]) {
+                self.swap(kept, i);
+                kept += 1;
+            }
+        }
+        self.truncate(kept);
+    }
+
+    /// Removes consecutive elements for which `same(curr, prev)` returns true,
+    /// where `prev` is the last element that was kept.
+    pub fn dedup_by<F>(&mut self, mut same: F)
+    where
+        F: FnMut(&mut T, &mut T) -> bool,
+    {
+        if self.len() <= 1 {
+            return;
+        }
+
+        let mut kept = 1;
+        for i in 1..self.len() {
+            let (front, back) = self.split_at_mut(i);
+            if !same(&mut back[0], &mut front[kept - 1]) {
+                self.swap(kept, i);
+                kept += 1;
+            }
+        }
+        self.truncate(kept);
+    }
+
+    pub fn dedup(&mut self)
+    where
+        T: PartialEq,
+    {
+        self.dedup_by(|curr, prev| curr == prev)
+    }
+
+    /// Removes the elements in `range` and returns them as an iterator.
+    /// The elements are removed even if the iterator is not fully consumed.
+    pub fn drain<R>(&mut self, range: R) -> Result<Drain<'_, T>, Error>
+    where
+        R: RangeBounds<usize>,
+    {
+        let start = match range.start_bound() {
+            Bound::Included(&i) => i,
+            Bound::Excluded(&i) => i + 1,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&i) => i + 1,
+            Bound::Excluded(&i) => i,
+            Bound::Unbounded => self.len(),
+        };
+        if start > end || end > self.len() {
+            return Err(anyhow!("range {start}..{end} out of bounds"));
+        }
+
+        match &mut self.storage {
+            Storage::Inline { len, arr } => {
+                // move the drained elements behind the kept ones, they are owned by `Drain` now
+                arr[start..*len].rotate_left(end - start);
+                let kept = *len - (end - start);
+                let drained = &mut arr[kept..*len];
+                *len = kept;
+                Ok(Drain::Inline(drained.iter_mut()))
+            }
+            Storage::Heap(a) => Ok(Drain::Heap(a.drain(start..end)?)),
+        }
+    }
+}
+
+/// Drain moves a range of elements out of a SmallList.
+pub enum Drain<'a, T> {
+    /// The drained slots, they are no longer part of the list.
+    Inline(slice::IterMut<'a, MaybeUninit<T>>),
+    Heap(array_list::Drain<'a, T>),
+}
+
+impl<T> Iterator for Drain<'_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        match self {
+            // SAFETY: each drained slot is initialized and read once
+            Self::Inline(slots) => slots.next().map(|s| unsafe { s.assume_init_read() }),
+            Self::Heap(d) => d.next(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self {
+            Self::Inline(slots) => slots.size_hint(),
+            Self::Heap(d) => d.size_hint(),
+        }
+    }
+}
+
+impl<T> DoubleEndedIterator for Drain<'_, T> {
+    fn next_back(&mut self) -> Option<T> {
+        match self {
+            // SAFETY: each drained slot is initialized and read once
+            Self::Inline(slots) => slots.next_back().map(|s| unsafe { s.assume_init_read() }),
+            Self::Heap(d) => d.next_back(),
+        }
+    }
+}
+
+impl<T> ExactSizeIterator for Drain<'_, T> {}
+
+impl<T> Drop for Drain<'_, T> {
+    fn drop(&mut self) {
+        if let Self::Inline(slots) = self {
+            for slot in slots {
+                // SAFETY: the slots that were not read are still initialized
+                unsafe { slot.assume_init_drop() };
+            }
+        }
+    }
+}
+
+impl<T, const N: usize> Deref for SmallList<T, N> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        match &self.storage {
+            // SAFETY: slots below `len` are initialized
+            Storage::Inline { len, arr } => unsafe {
+                slice::from_raw_parts(arr.as_ptr().cast(), *len)
+            },
+            Storage::Heap(a) => a,
+        }
+    }
+}
+
+impl<T, const N: usize> DerefMut for SmallList<T, N> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        match &mut self.storage {
+            // SAFETY: slots below `len` are initialized
+            Storage::Inline { len, arr } => unsafe {
+                slice::from_raw_parts_mut(arr.as_mut_ptr().cast(), *len)
+            },
+            Storage::Heap(a) => a,
+        }
+    }
+}
+
+impl<T, const N: usize> Drop for SmallList<T, N> {
+    fn drop(&mut self) {
+        if let Storage::Inline { .. } = self.storage {
+            self.clear();
+        }
+    }
+}
+
+impl<T, const N: usize> Default for SmallList<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, I, const N: usize> Index<I> for SmallList<T, N>
+where
+    I: SliceIndex<[T]>,
+{
+    type Output = I::Output;
+
+    fn index(&self, index: I) -> &Self::Output {
+        &(**self)[index]
+    }
+}
+
+impl<T, I, const N: usize> IndexMut<I> for SmallList<T, N>
+where
+    I: SliceIndex<[T]>,
+{
+    fn index_mut(&mut self, index: I) -> &mut Self::Output {
+        &mut (**self)[index]
+    }
+}
+
+impl<T, const N: usize> Extend<T> for SmallList<T, N> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+
+        for val in iter {
+            self.push(val);
+        }
+    }
+}
+
+impl<T, const N: usize> FromIterator<T> for SmallList<T, N> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut l = Self::new();
+        l.extend(iter);
+        l
+    }
+}
+
+impl<T, const N: usize> Clone for SmallList<T, N>
+where
+    T: Clone,
+{
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T, const N: usize> Debug for SmallList<T, N>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T, U, const N: usize> PartialEq<U> for SmallList<T, N>
+where
+    T: PartialEq,
+    U: AsRef<[T]>,
+{
+    fn eq(&self, other: &U) -> bool {
+        **self == *other.as_ref()
+    }
+}
+
+impl<T, const N: usize> Eq for SmallList<T, N> where T: Eq {}
+
+impl<T, const N: usize> AsRef<[T]> for SmallList<T, N> {
+    fn as_ref(&self) -> &[T] {
+        self
+    }
+}
+
+/// IntoIter moves the values out of a SmallList from front to back.
+pub enum IntoIter<T, const N: usize> {
+    /// Slots between `start` and `end` are initialized.
+    Inline {
+        start: usize,
+        end: usize,
+        arr: [MaybeUninit<T>; N],
+    },
+    Heap(array_list::IntoIter<T>),
+}
+
+impl<T, const N: usize> Iterator for IntoIter<T, N> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        match self {
+            Self::Inline { start, end, arr } => {
+                if start == end {
+                    return None;
+                }
+                *start += 1;
+                // SAFETY: slots between `start` and `end` are initialized
+                Some(unsafe { arr[*start - 1].assume_init_read() })
+            }
+            Self::Heap(it) => it.next(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self {
+            Self::Inline { start, end, .. } => (end - start, Some(end - start)),
+            Self::Heap(it) => it.size_hint(),
+        }
+    }
+}
+
+impl<T, const N: usize> DoubleEndedIterator for IntoIter<T, N> {
+    fn next_back(&mut self) -> Option<T> {
+        match self {
+            Self::Inline { start, end, arr } => {
+                if start == end {
+                    return None;
+                }
+                *end -= 1;
+                // SAFETY: slots between `start` and `end` are initialized
+                Some(unsafe { arr[*end].assume_init_read() })
+            }
+            Self::Heap(it) => it.next_back(),
+        }
+    }
+}
+
+impl<T, const N: usize> ExactSizeIterator for IntoIter<T, N> {}
+
+impl<T, const N: usize> Drop for IntoIter<T, N> {
+    fn drop(&mut self) {
+        if let Self::Inline { start, end, arr } = self {
+            for slot in &mut arr[*start..*end] {
+                // SAFETY: slots between `start` and `end` are initialized
+                unsafe { slot.assume_init_drop() };
+            }
+        }
+    }
+}
+
+impl<T, const N: usize> IntoIterator for SmallList<T, N> {
+    type Item = T;
+    type IntoIter = IntoIter<T, N>;
+
+    fn into_iter(mut self) -> IntoIter<T, N> {
+        let storage = mem::replace(
+            &mut self.storage,
+            Storage::Inline {
+                len: 0,
+                arr: [const { MaybeUninit::uninit() }; N],
+            },
+        );
+        match storage {
+            Storage::Inline { len, arr } => IntoIter::Inline {
+                start: 0,
+                end: len,
+                arr,
+            },
+            Storage::Heap(a) => IntoIter::Heap(a.into_iter()),
+        }
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a SmallList<T, N> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a mut SmallList<T, N> {
+    type Item = &'a mut T;
+    type IntoIter = slice::IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        let mut a = SmallList::<i32, 4>::new();
+
+        a.push(0);
+        a.push(1);
+        a.push(2);
+        a.push(3);
+        assert!(a.is_inline());
+
+        assert!(a.at(0).is_ok_and(|i| *i == 0));
+        assert!(a.at(3).is_ok_and(|i| *i == 3));
+        assert!(a.at(4).is_err());
+
+        assert!(a.pop().is_ok_and(|i| i == 3));
+        assert!(a.pop().is_ok_and(|i| i == 2));
+        assert!(a.pop().is_ok_and(|i| i == 1));
+        assert!(a.pop().is_ok_and(|i| i == 0));
+        assert!(a.pop().is_err());
+    }
+
+    #[test]
+    fn spills_to_heap() {
+        let mut a = SmallList::<String, 2>::new();
+        for i in 0..5 {
+            a.push(i.to_string());
+        }
+
+        assert!(!a.is_inline());
+        assert_eq!(a.join(","), "0,1,2,3,4");
+
+        a.truncate(2);
+        a.shrink_to_fit();
+        assert!(a.is_inline());
+        assert_eq!(a.join(","), "0,1");
+    }
+
+    #[test]
+    fn editing() {
+        let mut a: SmallList<i32, 8> = (0..5).collect();
+
+        assert!(a.insert(2, 10).is_ok());
+        assert!(a.insert(7, 11).is_err());
+        assert!(a.remove(0).is_ok_and(|i| i == 0));
+        assert!(a.swap_remove(0).is_ok_and(|i| i == 1));
+        assert_eq!(a, [4, 10, 2, 3]);
+
+        a.extend([3, 3, 5]);
+        a.dedup();
+        assert_eq!(a, [4, 10, 2, 3, 5]);
+        a.retain(|i| i % 2 == 0);
+        assert_eq!(a, [4, 10, 2]);
+        assert!(a.is_inline());
+
+        a.extend(0..10);
+        assert!(!a.is_inline());
+        a.retain(|i| *i > 5);
+        assert_eq!(a, [10, 6, 7, 8, 9]);
+    }
+
+    #[test]
+    fn drain() {
+        let mut a: SmallList<String, 8> = (0..6).map(|i| i.to_string()).collect();
+
+        let mut it = a.drain(1..4).unwrap();
+        assert!(it.next().is_some_and(|s| s == "1"));
+        assert!(it.next_back().is_some_and(|s| s == "3"));
+        drop(it);
+        assert_eq!(a.join(","), "0,4,5");
+
+        a.extend((6..12).map(|i| i.to_string()));
+        let drained: Vec<_> = a.drain(..3).unwrap().collect();
+        assert_eq!(drained, ["0", "4", "5"]);
+        assert_eq!(a.len(), 6);
+        assert!(a.drain(5..7).is_err());
+    }
+
+    #[test]
+    fn drops_values() {
+        let counter = Rc::new(());
+        let mut a: SmallList<_, 4> = (0..3).map(|_| Rc::clone(&counter)).collect();
+        let b = a.clone();
+        drop(a.drain(..1).unwrap());
+        assert_eq!(Rc::strong_count(&counter), 6);
+
+        let mut it = a.into_iter();
+        it.next();
+        drop(it);
+        drop(b);
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+
+    #[test]
+    fn sorts_as_slice() {
+        let mut a: SmallList<i32, 4> = [3, 1, 2].into_iter().collect();
+
+        a.sort();
+        assert_eq!(a, [1, 2, 3]);
+        assert_eq!(a[1], 2);
+        assert_eq!(a.into_iter().rev().collect::<Vec<_>>(), [3, 2, 1]);
+    }
+
+    #[cfg(feature = "bench")]
+    mod bench {
+        use super::*;
+
+        extern crate test;
+        use test::{black_box, Bencher};
+
+        #[bench]
+        fn bench_small_list_push_4(b: &mut Bencher) {
+            b.iter(|| {
+                let mut a = SmallList::<u64, 8>::new();
+                for i in 0..4 {
+                    a.push(black_box(i));
+                }
+                a
+            })
+        }
+
+        #[bench]
+        fn bench_array_list_push_4(b: &mut Bencher) {
+            b.iter(|| {
+                let mut a = ArrayList::<u64>::new();
+                for i in 0..4 {
+                    a.push(black_box(i));
+                }
+                a
+            })
+        }
+
+        #[bench]
+        fn bench_small_list_push_100(b: &mut Bencher) {
+            b.iter(|| {
+                let mut a = SmallList::<u64, 8>::new();
+                for i in 0..100 {
+                    a.push(black_box(i));
+                }
+                a
+            })
+        }
+
+        #[bench]
+        fn bench_array_list_push_100(b: &mut Bencher) {
+            b.iter(|| {
+                let mut a = ArrayList::<u64>::new();
+                for i in 0..100 {
+                    a.push(black_box(i));
+                }
+                a
+            })
+        }
+    }
+}
@@ -0,0 +1,353 @@
+use std::{
+    cell::UnsafeCell,
+    iter,
+    mem::MaybeUninit,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+/// Shared is the fixed-size ring of a single-producer/single-consumer channel.
+/// `head` and `tail` count the popped and pushed elements and only ever grow (wrapping),
+/// the slot of a count is `count & mask`. The ring has a power of two slots, so that
+/// consecutive counts stay in consecutive slots when they wrap around `usize::MAX`,
+/// which `count % capacity` would not for other capacities.
+/// Only the consumer writes `head` and only the producer writes `tail`.
+struct Shared<T> {
+    head: AtomicUsize,
+    tail: AtomicUsize,
+    capacity: usize,
+    mask: usize,
+    arr: Box<[UnsafeCell<MaybeUninit<T>>]>,
+}
+
+// SAFETY: a slot is only accessed by the producer before it publishes it with `tail`,
+// and only by the consumer after that until it releases it with `head`
+unsafe impl<T: Send> Send for Shared<T> {}
+unsafe impl<T: Send> Sync for Shared<T> {}
+
+impl<T> Shared<T> {
+    fn slot(&self, count: usize) -> *mut MaybeUninit<T> {
+        self.arr[count & self.mask].get()
+    }
+}
+
+impl<T> Drop for Shared<T> {
+    fn drop(&mut self) {
+        let head = *self.head.get_mut();
+        let tail = *self.tail.get_mut();
+        for count in 0..tail.wrapping_sub(head) {
+            // SAFETY: slots between `head` and `tail` are initialized
+            unsafe { (*self.slot(head.wrapping_add(count))).assume_init_drop() };
+        }
+    }
+}
+
+/// Stores a count into an atomic when dropped, so that a batch is published
+/// even if the iterator or the `Extend` implementation panics midway.
+struct Publish<'a> {
+    count: usize,
+    target: &'a AtomicUsize,
+}
+
+impl Drop for Publish<'_> {
+    fn drop(&mut self) {
+        self.target.store(self.count, Ordering::Release);
+    }
+}
+
+/// Creates a wait-free channel that holds at most `capacity` elements.
+/// The two handles can be moved to different threads.
+pub fn channel<T>(capacity: usize) -> (Producer<T>, Consumer<T>) {
+    channel_at(capacity, 0)
+}
+
+/// Creates a channel whose counters start at `count` instead of 0.
+fn channel_at<T>(capacity: usize, count: usize) -> (Producer<T>, Consumer<T>) {
+    let slots = capacity.next_power_of_two();
+    let shared = Arc::new(Shared {
+        head: AtomicUsize::new(count),
+        tail: AtomicUsize::new(count),
+        capacity,
+        mask: slots - 1,
+        arr: (0..slots)
+            .map(|_| UnsafeCell::new(MaybeUninit::uninit()))
+            .collect(),
+    });
+
+    (
+        Producer {
+            shared: Arc::clone(&shared),
+        },
+        Consumer { shared },
+    )
+}
+
+pub struct Producer<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> Producer<T> {
+    pub fn capacity(&self) -> usize {
+        self.shared.capacity
+    }
+
+    /// Number of elements that can be pushed before the consumer catches up.
+    pub fn free(&self) -> usize {
+        let head = self.shared.head.load(Ordering::Acquire);
+        let tail = self.shared.tail.load(Ordering::Relaxed);
+        self.shared.capacity - tail.wrapping_sub(head)
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.free() == 0
+    }
+
+    /// Pushes `val`, or hands it back if the channel is full.
+    pub fn push(&mut self, val: T) -> Result<(), T> {
+        if self.is_full() {
+            return Err(val);
+        }
+
+        let tail = self.shared.tail.load(Ordering::Relaxed);
+        // SAFETY: the slot is free and only the producer writes to free slots
+        unsafe { (*self.shared.slot(tail)).write(val) };
+        self.shared
+            .tail
+            .store(tail.wrapping_add(1), Ordering::Release);
+        Ok(())
+    }
+
+    /// Pushes values from `vals` until the channel is full and returns how many were pushed.
+    /// Values are only taken from the iterator when there is room for them,
+    /// and they become visible to the consumer all at once.
+    pub fn push_batch<I>(&mut self, vals: &mut I) -> usize
+    where
+        I: Iterator<Item = T>,
+    {
+        let free = self.free();
+        let tail = self.shared.tail.load(Ordering::Relaxed);
+        let mut publish = Publish {
+            count: tail,
+            target: &self.shared.tail,
+        };
+
+        for val in vals.take(free) {
+            // SAFETY: the slot is free and only the producer writes to free slots
+            unsafe { (*self.shared.slot(publish.count)).write(val) };
+            publish.count = publish.count.wrapping_add(1);
+        }
+
+        publish.count.wrapping_sub(tail)
+    }
+}
+
+pub struct Consumer<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> Consumer<T> {
+    pub fn capacity(&self) -> usize {
+        self.shared.capacity
+    }
+
+    /// Number of elements that can be popped right now.
+    pub fn len(&self) -> usize {
+        let head = self.shared.head.load(Ordering::Relaxed);
+        let tail = self.shared.tail.load(Ordering::Acquire);
+        tail.wrapping_sub(head)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let head = self.shared.head.load(Ordering::Relaxed);
+        // SAFETY: the slot was published by the producer and is only read once
+        let val = unsafe { (*self.shared.slot(head)).assume_init_read() };
+        self.shared
+            .head
+            .store(head.wrapping_add(1), Ordering::Release);
+        Some(val)
+    }
+
+    /// Pops up to `max` elements into `out` and returns how many were popped.
+    /// The slots are handed back to the producer all at once.
+    pub fn pop_batch<E>(&mut self, out: &mut E, max: usize) -> usize
+    where
+        E: Extend<T>,
+    {
+        let head = self.shared.head.load(Ordering::Relaxed);
+        let end = head.wrapping_add(self.len().min(max));
+        let mut publish = Publish {
+            count: head,
+            target: &self.shared.head,
+        };
+
+        let shared = &self.shared;
+        out.extend(iter::from_fn(|| {
+            if publish.count == end {
+                return None;
+            }
+            // SAFETY: the slot was published by the producer and is only read once
+            let val = unsafe { (*shared.slot(publish.count)).assume_init_read() };
+            publish.count = publish.count.wrapping_add(1);
+            Some(val)
+        }));
+
+        publish.count.wrapping_sub(head)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{rc::Rc, thread};
+
+    use super::*;
+    use crate::ArrayList;
+
+    #[test]
+    fn it_works() {
+        let (mut p, mut c) = channel(3);
+
+        assert_eq!(c.pop(), None);
+        assert!(p.push(0).is_ok());
+        assert!(p.push(1).is_ok());
+        assert!(p.push(2).is_ok());
+        assert!(p.is_full());
+        assert_eq!(p.push(3), Err(3));
+
+        assert_eq!(c.len(), 3);
+        assert_eq!(c.pop(), Some(0));
+        assert!(p.push(3).is_ok());
+        for i in 1..4 {
+            assert_eq!(c.pop(), Some(i));
+        }
+        assert!(c.is_empty());
+    }
+
+    #[test]
+    fn counters_wrap_around() {
+        let (mut p, mut c) = channel_at(3, usize::MAX - 1);
+
+        assert!(p.push(0).is_ok());
+        assert!(p.push(1).is_ok());
+        assert!(p.push(2).is_ok());
+        assert!(p.is_full());
+        assert_eq!(c.len(), 3);
+        assert_eq!(c.pop(), Some(0));
+        assert!(p.push(3).is_ok());
+        assert_eq!(p.push(4), Err(4));
+        for i in 1..4 {
+            assert_eq!(c.pop(), Some(i));
+        }
+
+        let mut vals = 4..10;
+        let mut out = Vec::new();
+        for _ in 0..2 {
+            assert_eq!(p.push_batch(&mut vals), 3);
+            assert_eq!(c.pop_batch(&mut out, 10), 3);
+        }
+        assert_eq!(out, [4, 5, 6, 7, 8, 9]);
+        assert!(c.is_empty());
+    }
+
+    #[test]
+    fn zero_capacity() {
+        let (mut p, mut c) = channel(0);
+
+        assert_eq!(p.push(1), Err(1));
+        assert_eq!(c.pop(), None);
+    }
+
+    #[test]
+    fn batches() {
+        let (mut p, mut c) = channel(4);
+        let mut vals = 0..10;
+
+        assert_eq!(p.push_batch(&mut vals), 4);
+        assert_eq!(p.push_batch(&mut vals), 0);
+        assert_eq!(vals.next(), Some(4));
+
+        let mut out = ArrayList::new();
+        assert_eq!(c.pop_batch(&mut out, 3), 3);
+        assert_eq!(p.push_batch(&mut vals), 3);
+        assert_eq!(c.pop_batch(&mut out, 10), 4);
+        assert_eq!(out, [0, 1, 2, 3, 5, 6, 7]);
+        assert_eq!(c.pop_batch(&mut out, 10), 0);
+    }
+
+    #[test]
+    fn drops_remaining() {
+        let counter = Rc::new(());
+        let (mut p, mut c) = channel(4);
+        for _ in 0..4 {
+            assert!(p.push(Rc::clone(&counter)).is_ok());
+        }
+        drop(c.pop());
+        drop(p);
+        assert_eq!(Rc::strong_count(&counter), 4);
+
+        drop(c);
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+
+    #[test]
+    fn threads() {
+        let n = 100_000;
+        let (mut p, mut c) = channel(64);
+
+        let producer = thread::spawn(move || {
+            for i in 0..n {
+                let mut val = i.to_string();
+                while let Err(v) = p.push(val) {
+                    val = v;
+                    thread::yield_now();
+                }
+            }
+        });
+
+        let mut next = 0;
+        while next < n {
+            match c.pop() {
+                Some(val) => {
+                    assert_eq!(val, next.to_string());
+                    next += 1;
+                }
+                None => thread::yield_now(),
+            }
+        }
+        producer.join().unwrap();
+        assert!(c.is_empty());
+    }
+
+    #[test]
+    fn threads_batches() {
+        let n = 100_000;
+        let (mut p, mut c) = channel(100);
+
+        let producer = thread::spawn(move || {
+            let mut vals = 0..n;
+            while !vals.is_empty() {
+                if p.push_batch(&mut vals) == 0 {
+                    thread::yield_now();
+                }
+            }
+        });
+
+        let mut out = Vec::with_capacity(n);
+        while out.len() < n {
+            if c.pop_batch(&mut out, 17) == 0 {
+                thread::yield_now();
+            }
+        }
+        producer.join().unwrap();
+        assert!(out.into_iter().eq(0..n));
+    }
+}
@@ -0,0 +1,237 @@
+use std::{
+    fmt::{Debug, Display},
+    ops::{Bound, RangeBounds},
+    str,
+};
+
+use anyhow::{anyhow, Error};
+
+use crate::gap_buffer::GapBuffer;
+
+/// TextBuffer is UTF-8 text in a gap buffer.
+/// Positions are byte offsets like for `str`, and the cursor is always on a char boundary,
+/// so the text before and after the cursor are valid strings on their own.
+#[derive(Clone, Default)]
+pub struct TextBuffer {
+    buf: GapBuffer<u8>,
+}
+
+impl TextBuffer {
+    pub fn new() -> Self {
+        Self {
+            buf: GapBuffer::new(),
+        }
+    }
+
+    /// Length in bytes.
+    pub fn len(&self) -> usize {
+        self.buf.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.buf.is_empty()
+    }
+
+    /// Number of chars, this is O(n).
+    pub fn char_count(&self) -> usize {
+        let (front, back) = self.as_strs();
+        front.chars().count() + back.chars().count()
+    }
+
+    pub fn cursor(&self) -> usize {
+        self.buf.cursor()
+    }
+
+    /// Returns the text before and after the cursor.
+    pub fn as_strs(&self) -> (&str, &str) {
+        let (front, back) = self.buf.as_slices();
+        // SAFETY: only whole strings are inserted and only whole chars removed,
+        // and the cursor never splits a char
+        unsafe {
+            (
+                str::from_utf8_unchecked(front),
+                str::from_utf8_unchecked(back),
+            )
+        }
+    }
+
+    fn is_char_boundary(&self, i: usize) -> bool {
+        let (front, back) = self.as_strs();
+        if i <= front.len() {
+            front.is_char_boundary(i)
+        } else {
+            back.is_char_boundary(i - front.len())
+        }
+    }
+
+    /// Moves the cursor to byte offset `i`, which has to be on a char boundary.
+    pub fn set_cursor(&mut self, i: usize) -> Result<(), Error> {
+        if i > self.len() {
+            return Err(anyhow!("index {i} out of bounds"));
+        }
+        if !self.is_char_boundary(i) {
+            return Err(anyhow!("index {i} is not a char boundary"));
+        }
+
+        self.buf.set_cursor(i)
+    }
+
+    /// Moves the cursor one char to the left and returns the char it passed.
+    pub fn move_left(&mut self) -> Option<char> {
+        let c = self.as_strs().0.chars().next_back()?;
+        self.buf.move_left(c.len_utf8());
+        Some(c)
+    }
+
+    /// Moves the cursor one char to the right and returns the char it passed.
+    pub fn move_right(&mut self) -> Option<char> {
+        let c = self.as_strs().1.chars().next()?;
+        self.buf.move_right(c.len_utf8());
+        Some(c)
+    }
+
+    pub fn insert_char(&mut self, c: char) {
+        self.buf.insert_slice(c.encode_utf8(&mut [0; 4]).as_bytes());
+    }
+
+    pub fn insert_str(&mut self, s: &str) {
+        self.buf.insert_slice(s.as_bytes());
+    }
+
+    /// Removes the char after the cursor.
+    pub fn delete(&mut self) -> Result<char, Error> {
+        let c = self
+            .as_strs()
+            .1
+            .chars()
+            .next()
+            .ok_or_else(|| anyhow!("cursor at end"))?;
+        self.buf.delete_n(c.len_utf8());
+        Ok(c)
+    }
+
+    /// Removes the char before the cursor.
+    pub fn backspace(&mut self) -> Result<char, Error> {
+        let c = self
+            .as_strs()
+            .0
+            .chars()
+            .next_back()
+            .ok_or_else(|| anyhow!("cursor at start"))?;
+        self.buf.move_left(c.len_utf8());
+        self.buf.delete_n(c.len_utf8());
+        Ok(c)
+    }
+
+    /// Copies the text between the byte offsets in `range`,
+    /// both ends have to be on char boundaries.
+    pub fn slice<R>(&self, range: R) -> Result<String, Error>
+    where
+        R: RangeBounds<usize>,
+    {
+        let start = match range.start_bound() {
+            Bound::Included(&i) => i,
+            Bound::Excluded(&i) => i + 1,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&i) => i + 1,
+            Bound::Excluded(&i) => i,
+            Bound::Unbounded => self.len(),
+        };
+        let (front, back) = self.buf.range(start..end)?;
+        if !self.is_char_boundary(start) || !self.is_char_boundary(end) {
+            return Err(anyhow!("range {start}..{end} splits a char"));
+        }
+
+        let mut s = String::with_capacity(end - start);
+        // SAFETY: both parts start and end on char boundaries
+        unsafe {
+            s.push_str(str::from_utf8_unchecked(front));
+            s.push_str(str::from_utf8_unchecked(back));
+        }
+        Ok(s)
+    }
+
+    pub fn chars(&self) -> impl DoubleEndedIterator<Item = char> + '_ {
+        let (front, back) = self.as_strs();
+        front.chars().chain(back.chars())
+    }
+}
+
+impl From<&str> for TextBuffer {
+    fn from(s: &str) -> Self {
+        let mut text = Self::new();
+        text.insert_str(s);
+        text
+    }
+}
+
+impl Display for TextBuffer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (front, back) = self.as_strs();
+        f.write_str(front)?;
+        f.write_str(back)
+    }
+}
+
+impl Debug for TextBuffer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(&self.to_string(), f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        let mut text = TextBuffer::from("hello world");
+
+        assert!(text.set_cursor(5).is_ok());
+        text.insert_char(',');
+        text.insert_str(" dear");
+        assert_eq!(text.to_string(), "hello, dear world");
+        assert_eq!(text.cursor(), 11);
+
+        assert!(text.backspace().is_ok_and(|c| c == 'r'));
+        assert!(text.delete().is_ok_and(|c| c == ' '));
+        assert_eq!(text.to_string(), "hello, deaworld");
+        assert!(text.slice(7..10).is_ok_and(|s| s == "dea"));
+    }
+
+    #[test]
+    fn multi_byte_chars() {
+        let mut text = TextBuffer::from("añ€😀");
+        assert_eq!(text.len(), 10);
+        assert_eq!(text.char_count(), 4);
+
+        assert_eq!(text.move_left(), Some('😀'));
+        assert_eq!(text.move_left(), Some('€'));
+        assert_eq!(text.cursor(), 3);
+        assert!(text.set_cursor(2).is_err());
+        assert!(text.set_cursor(11).is_err());
+        assert!(text.slice(1..4).is_err());
+        assert!(text.slice(1..6).is_ok_and(|s| s == "ñ€"));
+
+        assert!(text.backspace().is_ok_and(|c| c == 'ñ'));
+        assert!(text.delete().is_ok_and(|c| c == '€'));
+        text.insert_char('ü');
+        assert_eq!(text.to_string(), "aü😀");
+        assert_eq!(text.move_right(), Some('😀'));
+        assert_eq!(text.move_right(), None);
+        assert!(text.delete().is_err());
+        assert!(text.chars().rev().eq("😀üa".chars()));
+    }
+
+    #[test]
+    fn moves_to_start() {
+        let mut text = TextBuffer::from("äbc");
+        while text.move_left().is_some() {}
+        assert_eq!(text.cursor(), 0);
+        assert!(text.backspace().is_err());
+        assert_eq!(text.as_strs(), ("", "äbc"));
+        assert_eq!(format!("{text:?}"), "\"äbc\"");
+    }
+}
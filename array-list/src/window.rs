@@ -0,0 +1,189 @@
+use std::ops::{Add, Sub};
+
+use anyhow::Error;
+
+use crate::ring_buffer::{Iter, RingBuffer};
+
+/// SlidingWindow keeps running aggregates over the values between the oldest
+/// and the newest pushed value.
+/// `mins` and `maxs` are monotonic queues, their fronts are the current min and max,
+/// so that every value is pushed and popped on them at most once.
+#[derive(Debug, Clone)]
+pub struct SlidingWindow<T> {
+    values: RingBuffer<T>,
+    mins: RingBuffer<T>,
+    maxs: RingBuffer<T>,
+    sum: T,
+}
+
+impl<T> SlidingWindow<T>
+where
+    T: Copy + PartialOrd + Default + Add<Output = T> + Sub<Output = T>,
+{
+    pub fn new() -> Self {
+        Self {
+            values: RingBuffer::new(),
+            mins: RingBuffer::new(),
+            maxs: RingBuffer::new(),
+            sum: T::default(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Adds `val` as the newest value of the window.
+    pub fn push(&mut self, val: T) {
+        self.values.push_back(val);
+
+        while self.mins.back().is_some_and(|m| *m > val) {
+            self.mins.pop_back();
+        }
+        self.mins.push_back(val);
+
+        while self.maxs.back().is_some_and(|m| *m < val) {
+            self.maxs.pop_back();
+        }
+        self.maxs.push_back(val);
+
+        self.sum = self.sum + val;
+    }
+
+    /// Expires the oldest value of the window.
+    pub fn deque(&mut self) -> Result<T, Error> {
+        let val = self.values.deque()?;
+
+        if self.mins.front().is_some_and(|m| *m == val) {
+            self.mins.pop_front();
+        }
+        if self.maxs.front().is_some_and(|m| *m == val) {
+            self.maxs.pop_front();
+        }
+
+        self.sum = self.sum - val;
+        Ok(val)
+    }
+
+    pub fn min(&self) -> Option<T> {
+        self.mins.front().copied()
+    }
+
+    pub fn max(&self) -> Option<T> {
+        self.maxs.front().copied()
+    }
+
+    /// For floats, the sum is updated incrementally and can drift from
+    /// the exact sum of the window over many pushes.
+    pub fn sum(&self) -> T {
+        self.sum
+    }
+
+    pub fn mean(&self) -> Option<f64>
+    where
+        T: Into<f64>,
+    {
+        if self.is_empty() {
+            return None;
+        }
+
+        Some(self.sum.into() / self.len() as f64)
+    }
+
+    /// Iterates from the oldest to the newest value.
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.values.iter()
+    }
+}
+
+impl<T> Default for SlidingWindow<T>
+where
+    T: Copy + PartialOrd + Default + Add<Output = T> + Sub<Output = T>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        let mut w = SlidingWindow::new();
+
+        assert_eq!(w.min(), None);
+        assert_eq!(w.mean(), None);
+
+        for v in [4, 2, 12, 3] {
+            w.push(v);
+        }
+        assert_eq!(w.min(), Some(2));
+        assert_eq!(w.max(), Some(12));
+        assert_eq!(w.sum(), 21);
+        assert_eq!(w.mean(), Some(5.25));
+
+        assert!(w.deque().is_ok_and(|v| v == 4));
+        assert!(w.deque().is_ok_and(|v| v == 2));
+        assert_eq!(w.min(), Some(3));
+        assert_eq!(w.max(), Some(12));
+        assert!(w.deque().is_ok_and(|v| v == 12));
+        assert_eq!(w.max(), Some(3));
+        assert!(w.deque().is_ok());
+        assert!(w.deque().is_err());
+        assert_eq!(w.sum(), 0);
+    }
+
+    #[test]
+    fn duplicates() {
+        let mut w = SlidingWindow::new();
+        for v in [5, 1, 5, 1] {
+            w.push(v);
+        }
+
+        assert!(w.deque().is_ok());
+        assert!(w.deque().is_ok());
+        assert_eq!(w.min(), Some(1));
+        assert_eq!(w.max(), Some(5));
+        assert!(w.deque().is_ok());
+        assert_eq!(w.min(), Some(1));
+        assert_eq!(w.max(), Some(1));
+    }
+
+    #[test]
+    fn matches_rescan() {
+        let values = [7, -3, 8, 8, 0, 2, -9, 14, 3, 3, -1, 6, 10, -4, 5, 5, 1];
+        let size = 4;
+        let mut w = SlidingWindow::new();
+
+        for (i, v) in values.iter().enumerate() {
+            w.push(*v);
+            if w.len() > size {
+                assert!(w.deque().is_ok_and(|old| old == values[i - size]));
+            }
+
+            let window = &values[i + 1 - w.len()..=i];
+            assert_eq!(w.min(), window.iter().copied().min());
+            assert_eq!(w.max(), window.iter().copied().max());
+            assert_eq!(w.sum(), window.iter().sum::<i32>());
+            assert!(w.iter().eq(window.iter()));
+        }
+    }
+
+    #[test]
+    fn floats() {
+        let mut w = SlidingWindow::new();
+        for v in [1.5, -0.5, 2.0] {
+            w.push(v);
+        }
+
+        assert_eq!(w.min(), Some(-0.5));
+        assert_eq!(w.max(), Some(2.0));
+        assert_eq!(w.mean(), Some(1.0));
+    }
+}
@@ -1,6 +1,6 @@
 #![allow(dead_code)]
 
-fn internal_search<T>(arr: &Vec<T>, lo: usize, hi: usize, v: T) -> Option<usize>
+fn internal_search<T>(arr: &[T], lo: usize, hi: usize, v: T) -> Option<usize>
 where
     T: Ord,
 {
@@ -18,14 +18,14 @@ where
     }
 }
 
-fn search_rec<T>(arr: Vec<T>, v: T) -> Option<usize>
+fn search_rec<T>(arr: &[T], v: T) -> Option<usize>
 where
     T: Ord,
 {
-    internal_search(&arr, 0, arr.len(), v)
+    internal_search(arr, 0, arr.len(), v)
 }
 
-fn search<T>(arr: Vec<T>, v: T) -> Option<usize>
+fn search<T>(arr: &[T], v: T) -> Option<usize>
 where
     T: Ord,
 {
@@ -50,52 +50,61 @@ where
 #[cfg(test)]
 mod test {
     use crate::*;
+    use array_list::ArrayList;
 
     #[test]
     fn test_middle() {
         let arr = vec![0, 1, 2, 3, 4];
-        assert_eq!(search(arr, 2), Some(2));
+        assert_eq!(search(&arr, 2), Some(2));
     }
 
     #[test]
     fn test_left_middle() {
         let arr = vec![0, 1, 2, 3, 4];
-        assert_eq!(search(arr, 1), Some(1));
+        assert_eq!(search(&arr, 1), Some(1));
     }
 
     #[test]
     fn test_not_found() {
         let arr = vec![0, 1, 2, 3, 4];
-        assert_eq!(search(arr, 5), None);
+        assert_eq!(search(&arr, 5), None);
     }
 
     #[test]
     fn test_even() {
         let arr = vec![0, 1, 2, 3];
-        assert_eq!(search(arr, 2), Some(2));
+        assert_eq!(search(&arr, 2), Some(2));
     }
 
     #[test]
     fn test_rec_middle() {
         let arr = vec![0, 1, 2, 3, 4];
-        assert_eq!(search_rec(arr, 2), Some(2));
+        assert_eq!(search_rec(&arr, 2), Some(2));
     }
 
     #[test]
     fn test_rec_left_middle() {
         let arr = vec![0, 1, 2, 3, 4];
-        assert_eq!(search_rec(arr, 1), Some(1));
+        assert_eq!(search_rec(&arr, 1), Some(1));
     }
 
     #[test]
     fn test_rec_not_found() {
         let arr = vec![0, 1, 2, 3, 4];
-        assert_eq!(search_rec(arr, 5), None);
+        assert_eq!(search_rec(&arr, 5), None);
     }
 
     #[test]
     fn test_rec_even() {
         let arr = vec![0, 1, 2, 3];
-        assert_eq!(search_rec(arr, 2), Some(2));
+        assert_eq!(search_rec(&arr, 2), Some(2));
+    }
+
+    #[test]
+    fn test_array_list() {
+        let arr: ArrayList<i32> = (0..10).collect();
+        assert_eq!(search(&arr, 7), Some(7));
+        assert_eq!(search_rec(&arr, 3), Some(3));
+        assert_eq!(search(&arr, 10), None);
     }
 }
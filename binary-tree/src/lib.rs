@@ -28,6 +28,19 @@ pub struct BinaryTree<T> {
     root: Link<T>,
 }
 
+impl<T> BinaryTree<T> {
+    /// Creates an empty tree, fill it with `insert`.
+    pub fn new() -> Self {
+        Self { root: None }
+    }
+}
+
+impl<T> Default for BinaryTree<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -46,16 +46,18 @@ where
     }
 }
 
-#[allow(dead_code)]
+/// The search tree API, so that the tree can be used and compared
+/// as an ordered set outside of this crate.
 impl<T> BinaryTree<T>
 where
     T: Ord,
 {
-    fn find(&self, value: T) -> bool {
+    pub fn find(&self, value: T) -> bool {
         walk_find(&self.root, value)
     }
 
-    fn insert(&mut self, value: T) {
+    /// Inserts `value`, duplicates go to the left subtree of their equal.
+    pub fn insert(&mut self, value: T) {
         walk_insert(&mut self.root, value)
     }
 }
@@ -82,7 +84,7 @@ mod test {
     #[test]
     fn insert() {
         let w = create_tree();
-        let mut g = BinaryTree { root: None };
+        let mut g = BinaryTree::new();
         g.insert(10);
         g.insert(0);
         g.insert(20);
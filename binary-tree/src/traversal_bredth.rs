@@ -1,4 +1,4 @@
-use std::collections::VecDeque;
+use array_list::RingBuffer;
 
 use crate::BinaryTree;
 
@@ -9,7 +9,7 @@ where
 {
     fn breadth_first(&self) -> Vec<T> {
         let mut res = Vec::new();
-        let mut q = VecDeque::from([&self.root]);
+        let mut q = RingBuffer::from([&self.root]);
 
         while !q.is_empty() {
             let curr = q.pop_front().expect("Only popping from non-empty queue");
@@ -24,7 +24,7 @@ where
     }
 
     fn breadth_first_search(&self, n: T) -> bool {
-        let mut q = VecDeque::from([&self.root]);
+        let mut q = RingBuffer::from([&self.root]);
 
         while !q.is_empty() {
             let curr = q.pop_front().expect("Only popping from non-empty queue");
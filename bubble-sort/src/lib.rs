@@ -1,5 +1,5 @@
 #[allow(dead_code)]
-fn sort<T>(arr: &mut Vec<T>)
+fn sort<T>(arr: &mut [T])
 where
     T: Ord,
 {
@@ -15,6 +15,7 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use array_list::ArrayList;
 
     #[test]
     fn it_works() {
@@ -29,4 +30,11 @@ mod tests {
         sort(&mut arr);
         assert_eq!(arr, [0, 1, 1, 2, 2, 3, 4, 5, 5, 6, 9, 20, 300]);
     }
+
+    #[test]
+    fn array_list() {
+        let mut arr: ArrayList<i32> = [1, 3, 2, 5, 9, 0].into_iter().collect();
+        sort(&mut arr);
+        assert_eq!(arr, [0, 1, 2, 3, 5, 9]);
+    }
 }
@@ -1,4 +1,6 @@
-use std::collections::{BinaryHeap, VecDeque};
+use std::collections::BinaryHeap;
+
+use array_list::RingBuffer;
 
 pub struct Edge {
     weight: i32,
@@ -99,12 +101,15 @@ impl Graph {
         }
 
         prev[tgt].map(|mut curr| {
-            let mut path = VecDeque::from([curr, tgt]);
+            let mut path = RingBuffer::from([curr, tgt]);
             while let Some(prev) = prev[curr] {
                 path.push_front(prev);
                 curr = prev;
             }
-            (path.into(), dist[tgt].expect("this has to be some"))
+            (
+                path.into_iter().collect(),
+                dist[tgt].expect("this has to be some"),
+            )
         })
     }
 }
@@ -1,4 +1,4 @@
-use std::collections::VecDeque;
+use array_list::RingBuffer;
 
 pub struct Graph {
     edges: Vec<Vec<Option<i32>>>,
@@ -28,10 +28,9 @@ impl Graph {
         let mut visited = vec![false; self.edges.len()];
         visited[src] = true;
         let mut prev = vec![None; self.edges.len()];
-        let mut queue = vec![src];
+        let mut queue = RingBuffer::from([src]);
 
-        while !queue.is_empty() {
-            let curr = queue.pop().expect("q is non-empty");
+        while let Some(curr) = queue.pop_front() {
             if curr == tgt {
                 break;
             }
@@ -47,12 +46,12 @@ impl Graph {
 
 fn build_path(tgt: usize, prev: Vec<Option<usize>>) -> Option<Vec<usize>> {
     prev[tgt].map(|mut curr| {
-        let mut path = VecDeque::from([curr, tgt]);
+        let mut path = RingBuffer::from([curr, tgt]);
         while let Some(prev) = prev[curr] {
             path.push_front(prev);
             curr = prev;
         }
-        path.into()
+        path.into_iter().collect()
     })
 }
 
@@ -60,16 +59,14 @@ fn walk_edge(
     curr: usize,
     i: usize,
     e: &Option<i32>,
-    queue: &mut Vec<usize>,
-    prev: &mut Vec<Option<usize>>,
-    visited: &mut Vec<bool>,
+    queue: &mut RingBuffer<usize>,
+    prev: &mut [Option<usize>],
+    visited: &mut [bool],
 ) {
-    if let Some(_) = e {
-        if !visited[i] {
-            queue.push(i);
-            prev[i] = Some(curr);
-            visited[i] = true;
-        }
+    if e.is_some() && !visited[i] {
+        queue.push_back(i);
+        prev[i] = Some(curr);
+        visited[i] = true;
     }
 }
 
@@ -0,0 +1,56 @@
+//! Compares the skip list with the BST of `binary-tree` and `BTreeMap`.
+
+#![feature(test)]
+
+extern crate test;
+
+use std::collections::BTreeMap;
+
+use binary_tree::BinaryTree;
+use linked_list::skip_list::SkipList;
+use rand::seq::SliceRandom;
+use test::{black_box, Bencher};
+
+const BENCH_LEN: usize = 1000;
+
+fn bench_keys() -> Vec<u32> {
+    let mut keys: Vec<_> = (0..BENCH_LEN as u32).collect();
+    keys.shuffle(&mut rand::thread_rng());
+    keys
+}
+
+#[bench]
+fn bench_skip_list_insert_find(b: &mut Bencher) {
+    let keys = bench_keys();
+    b.iter(|| {
+        let mut list = SkipList::new();
+        for &key in &keys {
+            list.insert(black_box(key), ());
+        }
+        keys.iter().filter(|key| list.contains_key(key)).count()
+    })
+}
+
+#[bench]
+fn bench_binary_tree_insert_find(b: &mut Bencher) {
+    let keys = bench_keys();
+    b.iter(|| {
+        let mut tree = BinaryTree::new();
+        for &key in &keys {
+            tree.insert(black_box(key));
+        }
+        keys.iter().filter(|key| tree.find(**key)).count()
+    })
+}
+
+#[bench]
+fn bench_btree_map_insert_find(b: &mut Bencher) {
+    let keys = bench_keys();
+    b.iter(|| {
+        let mut map = BTreeMap::new();
+        for &key in &keys {
+            map.insert(black_box(key), ());
+        }
+        keys.iter().filter(|key| map.contains_key(key)).count()
+    })
+}
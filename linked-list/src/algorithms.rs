@@ -0,0 +1,554 @@
+//! Classic linked list algorithms for `single::List` and `double::List`.
+//! They relink the existing nodes, no value is moved or copied.
+
+use std::{cell::RefCell, cmp::Ordering, mem, rc::Rc};
+
+use anyhow::{anyhow, Error};
+
+use crate::{double, single};
+
+/// Detaches everything after the first `n` nodes.
+fn split_single<T>(link: &mut single::Link<T>, n: usize) -> single::Link<T> {
+    let mut curr = link;
+    for _ in 0..n {
+        curr = &mut curr.as_mut()?.next;
+    }
+    curr.take()
+}
+
+/// Merges two sorted chains, on equal values the node of `a` comes first.
+fn merge_single<T, F>(
+    mut a: single::Link<T>,
+    mut b: single::Link<T>,
+    cmp: &mut F,
+) -> single::Link<T>
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let mut head = None;
+    let mut tail = &mut head;
+    loop {
+        let mut node = match (a.take(), b.take()) {
+            (Some(x), Some(mut y)) if cmp(&y.val, &x.val) == Ordering::Less => {
+                a = Some(x);
+                b = y.next.take();
+                y
+            }
+            (Some(mut x), y @ Some(_)) => {
+                a = x.next.take();
+                b = y;
+                x
+            }
+            (rest, None) | (None, rest) => {
+                *tail = rest;
+                return head;
+            }
+        };
+        node.next = None;
+        tail = &mut tail.insert(node).next;
+    }
+}
+
+fn sort_single<T, F>(mut head: single::Link<T>, len: usize, cmp: &mut F) -> single::Link<T>
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    if len <= 1 {
+        return head;
+    }
+
+    let back = split_single(&mut head, len / 2);
+    let front = sort_single(head, len / 2, cmp);
+    let back = sort_single(back, len - len / 2, cmp);
+    merge_single(front, back, cmp)
+}
+
+impl<T> single::List<T> {
+    pub fn reverse(&mut self) {
+        let mut reversed = None;
+        let mut curr = self.head.take();
+        while let Some(mut node) = curr {
+            curr = mem::replace(&mut node.next, reversed);
+            reversed = Some(node);
+        }
+        self.head = reversed;
+    }
+
+    /// Stable merge sort.
+    pub fn sort(&mut self)
+    where
+        T: Ord,
+    {
+        self.sort_by(T::cmp);
+    }
+
+    pub fn sort_by<F>(&mut self, mut cmp: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let len = self.iter().count();
+        self.head = sort_single(self.head.take(), len, &mut cmp);
+    }
+
+    /// Merges the sorted `other` into this sorted list.
+    pub fn merge(&mut self, mut other: single::List<T>)
+    where
+        T: Ord,
+    {
+        self.head = merge_single(self.head.take(), other.head.take(), &mut T::cmp);
+    }
+
+    /// Returns the middle element, the first of the two middle ones for even lengths.
+    /// The fast pointer moves two nodes for every node of the slow one.
+    pub fn middle(&self) -> Option<&T> {
+        let mut slow = self.head.as_deref()?;
+        let mut fast = slow;
+        while let Some(next) = fast.next.as_deref().and_then(|node| node.next.as_deref()) {
+            fast = next;
+            slow = slow.next.as_deref().expect("slow is behind fast");
+        }
+        Some(&slow.val)
+    }
+
+    /// Removes the `k`th element from the end, where `k = 1` is the last one.
+    /// The list does not know its length, so this takes two passes: a pointer that starts
+    /// `k` nodes ahead counts the nodes in front of the element on its way to the end,
+    /// then `delete_at` walks to the element from the head.
+    pub fn remove_kth_from_end(&mut self, k: usize) -> Result<T, Error> {
+        if k == 0 {
+            return Err(anyhow!("index {k} is out of bounds"));
+        }
+
+        let mut lead = self.head.as_deref();
+        for _ in 0..k {
+            lead = lead
+                .ok_or_else(|| anyhow!("index {k} is out of bounds"))?
+                .next
+                .as_deref();
+        }
+        let mut i = 0;
+        while let Some(node) = lead {
+            lead = node.next.as_deref();
+            i += 1;
+        }
+
+        self.delete_at(i)
+    }
+}
+
+type DoubleNode<T> = Rc<RefCell<double::Node<T>>>;
+
+/// Detaches everything after the first `n` nodes of a chain linked by `next` only.
+fn split_double<T>(head: &DoubleNode<T>, n: usize) -> double::Link<T> {
+    let mut curr = head.clone();
+    for _ in 1..n {
+        let next = curr.borrow().next.clone();
+        curr = next?;
+    }
+    let back = curr.borrow_mut().next.take();
+    back
+}
+
+/// Merges two sorted chains by their `next` links, on equal values the node of `a` comes first.
+/// The `prev` links are fixed afterwards by `relink`.
+fn merge_double<T, F>(
+    mut a: double::Link<T>,
+    mut b: double::Link<T>,
+    cmp: &mut F,
+) -> double::Link<T>
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let mut head: double::Link<T> = None;
+    let mut tail: Option<DoubleNode<T>> = None;
+    while let (Some(x), Some(y)) = (&a, &b) {
+        let take_b = cmp(&y.borrow().val, &x.borrow().val) == Ordering::Less;
+        let from = if take_b { &mut b } else { &mut a };
+        let node = from.take().expect("both chains are non-empty");
+        *from = node.borrow_mut().next.take();
+
+        match &tail {
+            None => head = Some(node.clone()),
+            Some(tail) => tail.borrow_mut().next = Some(node.clone()),
+        }
+        tail = Some(node);
+    }
+
+    let rest = a.or(b);
+    match &tail {
+        None => rest,
+        Some(tail) => {
+            tail.borrow_mut().next = rest;
+            head
+        }
+    }
+}
+
+fn sort_double<T, F>(head: double::Link<T>, len: usize, cmp: &mut F) -> double::Link<T>
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let Some(first) = head.as_ref().filter(|_| len > 1) else {
+        return head;
+    };
+
+    let back = split_double(first, len / 2);
+    let front = sort_double(head, len / 2, cmp);
+    let back = sort_double(back, len - len / 2, cmp);
+    merge_double(front, back, cmp)
+}
+
+impl<T> double::List<T> {
+    /// Sets the `prev` links and the tail along the `next` links from `head`.
+    fn relink(&mut self, head: double::Link<T>) {
+        let mut prev: double::Link<T> = None;
+        let mut curr = head.clone();
+        while let Some(node) = curr {
+            node.borrow_mut().prev = prev;
+            curr = node.borrow().next.clone();
+            prev = Some(node);
+        }
+        self.head = head;
+        self.tail = prev;
+    }
+
+    /// Swaps the links of every node.
+    pub fn reverse(&mut self) {
+        let mut curr = self.head.clone();
+        while let Some(node) = curr {
+            let node = &mut *node.borrow_mut();
+            mem::swap(&mut node.prev, &mut node.next);
+            curr = node.prev.clone();
+        }
+        mem::swap(&mut self.head, &mut self.tail);
+    }
+
+    /// Stable merge sort.
+    pub fn sort(&mut self)
+    where
+        T: Ord,
+    {
+        self.sort_by(T::cmp);
+    }
+
+    pub fn sort_by<F>(&mut self, mut cmp: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        self.tail = None;
+        let head = sort_double(self.head.take(), self.len, &mut cmp);
+        self.relink(head);
+    }
+
+    /// Merges the sorted `other` into this sorted list.
+    pub fn merge(&mut self, mut other: double::List<T>)
+    where
+        T: Ord,
+    {
+        self.tail = None;
+        other.tail = None;
+        self.len += mem::take(&mut other.len);
+        let head = merge_double(self.head.take(), other.head.take(), &mut T::cmp);
+        self.relink(head);
+    }
+
+    /// Returns the middle element, the first of the two middle ones for even lengths.
+    /// The fast pointer moves two nodes for every node of the slow one.
+    pub fn middle(&self) -> Option<&T> {
+        let mut slow = self.head.clone()?;
+        let mut fast = slow.clone();
+        loop {
+            let next = fast.borrow().next.clone();
+            let Some(next) = next.and_then(|node| node.borrow().next.clone()) else {
+                break;
+            };
+            fast = next;
+            let next = slow.borrow().next.clone().expect("slow is behind fast");
+            slow = next;
+        }
+        // SAFETY: the list is borrowed for as long as the reference
+        Some(unsafe { double::val_ref(&slow) })
+    }
+
+    /// Removes the `k`th element from the end, where `k = 1` is the last one.
+    /// The list knows its tail, so this walks back `k` nodes.
+    pub fn remove_kth_from_end(&mut self, k: usize) -> Result<T, Error> {
+        if k == 0 || k > self.len {
+            return Err(anyhow!("index {k} is out of bounds"));
+        }
+
+        let mut cursor = self.cursor_back_mut();
+        for _ in 1..k {
+            cursor.move_prev();
+        }
+        cursor
+            .remove_current()
+            .ok_or_else(|| anyhow!("index {k} is out of bounds"))
+    }
+}
+
+/// Cycle tells where a chain of nodes runs into a loop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    /// Number of steps from the first node to the first node on the loop.
+    pub start: usize,
+    pub len: usize,
+}
+
+/// Finds a cycle with Floyd's tortoise and hare in a chain that starts at `head`
+/// and continues with `next` until it returns `None`.
+/// The nodes can be anything that identifies a node, e.g. indices into a `Vec`.
+pub fn find_cycle<N, F>(head: N, next: F) -> Option<Cycle>
+where
+    N: Clone + PartialEq,
+    F: Fn(&N) -> Option<N>,
+{
+    let mut slow = head.clone();
+    let mut fast = head.clone();
+    loop {
+        fast = next(&fast)?;
+        fast = next(&fast)?;
+        slow = next(&slow).expect("slow is behind fast");
+        if slow == fast {
+            break;
+        }
+    }
+
+    // the distance from the head to the loop equals the distance
+    // from the meeting point to the loop, going around it
+    let mut start = 0;
+    let mut slow = head;
+    while slow != fast {
+        slow = next(&slow).expect("the chain is a loop from here");
+        fast = next(&fast).expect("the chain is a loop from here");
+        start += 1;
+    }
+
+    let mut len = 1;
+    let mut probe = next(&slow).expect("the chain is a loop from here");
+    while probe != slow {
+        probe = next(&probe).expect("the chain is a loop from here");
+        len += 1;
+    }
+
+    Some(Cycle { start, len })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use rand::Rng;
+
+    use super::*;
+
+    fn random_vec(len: usize) -> Vec<i32> {
+        let mut rng = rand::thread_rng();
+        (0..len).map(|_| rng.gen_range(0..20)).collect()
+    }
+
+    fn single_from<T>(v: &[T]) -> single::List<T>
+    where
+        T: Clone,
+    {
+        let mut l = single::List::new();
+        for val in v.iter().rev() {
+            l.push(val.clone());
+        }
+        l
+    }
+
+    fn double_from<T>(v: &[T]) -> double::List<T>
+    where
+        T: Clone,
+    {
+        let mut l = double::List::new();
+        for val in v {
+            l.push_back(val.clone());
+        }
+        l
+    }
+
+    /// Checks the `prev` links and the tail as well.
+    fn double_equals<T>(l: &double::List<T>, v: &[T]) -> bool
+    where
+        T: PartialEq,
+    {
+        l.len() == v.len() && l.iter().eq(v) && l.iter().rev().eq(v.iter().rev())
+    }
+
+    #[test]
+    fn reverse() {
+        for len in [0, 1, 2, 7] {
+            let v = random_vec(len);
+            let mut rev = v.clone();
+            rev.reverse();
+
+            let mut s = single_from(&v);
+            s.reverse();
+            assert!(s.iter().eq(&rev));
+
+            let mut d = double_from(&v);
+            d.reverse();
+            assert!(double_equals(&d, &rev));
+        }
+    }
+
+    #[test]
+    fn sort() {
+        for len in [0, 1, 2, 3, 100, 1000] {
+            let mut v = random_vec(len);
+            let mut s = single_from(&v);
+            let mut d = double_from(&v);
+            s.sort();
+            d.sort();
+            v.sort();
+            assert!(s.iter().eq(&v));
+            assert!(double_equals(&d, &v));
+        }
+    }
+
+    #[test]
+    fn sort_is_stable() {
+        let v: Vec<_> = random_vec(200).into_iter().zip(0..).collect();
+        let mut s = single_from(&v);
+        let mut d = double_from(&v);
+        s.sort_by(|a, b| a.0.cmp(&b.0));
+        d.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut sorted = v.clone();
+        sorted.sort_by_key(|pair| pair.0);
+        assert!(s.iter().eq(&sorted));
+        assert!(double_equals(&d, &sorted));
+    }
+
+    #[test]
+    fn sort_relinks_nodes() {
+        let mut d = double_from(&[3, 1, 2]);
+        let first = d.get(0).unwrap() as *const i32;
+        d.sort();
+        assert_eq!(d.get(2).unwrap() as *const i32, first);
+    }
+
+    #[test]
+    fn merge() {
+        for (a, b) in [(0, 0), (0, 5), (5, 0), (30, 50)] {
+            let mut a = random_vec(a);
+            let mut b = random_vec(b);
+            a.sort();
+            b.sort();
+            let mut merged: Vec<_> = a.iter().chain(&b).copied().collect();
+            merged.sort();
+
+            let mut s = single_from(&a);
+            s.merge(single_from(&b));
+            assert!(s.iter().eq(&merged));
+
+            let mut d = double_from(&a);
+            d.merge(double_from(&b));
+            assert!(double_equals(&d, &merged));
+        }
+    }
+
+    #[test]
+    fn middle() {
+        assert!(single::List::<i32>::new().middle().is_none());
+        assert!(double::List::<i32>::new().middle().is_none());
+        for len in 1..10 {
+            let v = random_vec(len);
+            let mid = &v[(len - 1) / 2];
+            assert_eq!(single_from(&v).middle(), Some(mid));
+            assert_eq!(double_from(&v).middle(), Some(mid));
+        }
+    }
+
+    #[test]
+    fn remove_kth_from_end() {
+        let v = random_vec(6);
+        for k in 1..=6 {
+            let mut expected = v.clone();
+            let removed = expected.remove(v.len() - k);
+
+            let mut s = single_from(&v);
+            assert!(s.remove_kth_from_end(k).is_ok_and(|x| x == removed));
+            assert!(s.iter().eq(&expected));
+
+            let mut d = double_from(&v);
+            assert!(d.remove_kth_from_end(k).is_ok_and(|x| x == removed));
+            assert!(double_equals(&d, &expected));
+        }
+
+        assert!(single_from(&v).remove_kth_from_end(0).is_err());
+        assert!(single_from(&v).remove_kth_from_end(7).is_err());
+        assert!(double_from(&v).remove_kth_from_end(0).is_err());
+        assert!(double_from(&v).remove_kth_from_end(7).is_err());
+    }
+
+    /// Finds the cycle by remembering the step at which every node was seen.
+    fn find_cycle_naive(next: &[Option<usize>]) -> Option<Cycle> {
+        let mut seen = HashMap::new();
+        let mut curr = 0;
+        for step in 0.. {
+            if let Some(first) = seen.insert(curr, step) {
+                return Some(Cycle {
+                    start: first,
+                    len: step - first,
+                });
+            }
+            curr = next[curr]?;
+        }
+        unreachable!()
+    }
+
+    #[test]
+    fn cycles_in_index_chains() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..200 {
+            let len = rng.gen_range(1..30);
+            let next: Vec<_> = (0..len)
+                .map(|_| rng.gen_bool(0.9).then(|| rng.gen_range(0..len)))
+                .collect();
+            assert_eq!(find_cycle(0, |&i| next[i]), find_cycle_naive(&next));
+        }
+
+        assert_eq!(find_cycle(0, |&i| (i < 3).then_some(i + 1)), None);
+        assert_eq!(
+            find_cycle(0, |&i| Some(if i == 4 { 2 } else { i + 1 })),
+            Some(Cycle { start: 2, len: 3 })
+        );
+    }
+
+    #[test]
+    fn cycles_in_node_chains() {
+        struct Node {
+            next: Option<Rc<RefCell<Node>>>,
+        }
+
+        #[derive(Clone)]
+        struct Ptr(Rc<RefCell<Node>>);
+
+        impl PartialEq for Ptr {
+            fn eq(&self, other: &Self) -> bool {
+                Rc::ptr_eq(&self.0, &other.0)
+            }
+        }
+
+        let nodes: Vec<_> = (0..5)
+            .map(|_| Rc::new(RefCell::new(Node { next: None })))
+            .collect();
+        for pair in nodes.windows(2) {
+            pair[0].borrow_mut().next = Some(pair[1].clone());
+        }
+        let next = |p: &Ptr| p.0.borrow().next.clone().map(Ptr);
+        assert_eq!(find_cycle(Ptr(nodes[0].clone()), next), None);
+
+        nodes[4].borrow_mut().next = Some(nodes[1].clone());
+        assert_eq!(
+            find_cycle(Ptr(nodes[0].clone()), next),
+            Some(Cycle { start: 1, len: 4 })
+        );
+
+        // break the cycle, so that the nodes are freed
+        nodes[4].borrow_mut().next = None;
+    }
+}
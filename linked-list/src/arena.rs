@@ -0,0 +1,848 @@
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    marker::PhantomData,
+    mem,
+    sync::atomic::{AtomicU32, Ordering},
+};
+
+use anyhow::{anyhow, Error};
+
+/// Marks the end of the list and of the free list.
+const NIL: u32 = u32::MAX;
+
+/// Source of the list ids, so that a handle is only resolved by the list that made it.
+static NEXT_LIST: AtomicU32 = AtomicU32::new(0);
+
+/// Handle refers to a node of a `List`. It stays valid until the node is removed,
+/// moved to another list or the list is compacted.
+/// Every node gets a new generation, so a stale handle is detected even if its slot
+/// holds a different node by now, and every list gets its own id, so that a handle
+/// of one list is not mistaken for a node of another.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Handle {
+    list: u32,
+    index: u32,
+    generation: u32,
+}
+
+struct Node<T> {
+    val: T,
+    prev: u32,
+    next: u32,
+}
+
+enum Slot<T> {
+    Used(Node<T>),
+    /// Holds the next free slot.
+    Free(u32),
+}
+
+struct Entry<T> {
+    generation: u32,
+    slot: Slot<T>,
+}
+
+/// List is a doubly linked list that keeps its nodes in one `Vec` and links them by index.
+/// Removed slots are put on a free list and reused by the next insert.
+pub struct List<T> {
+    id: u32,
+    entries: Vec<Entry<T>>,
+    free: u32,
+    head: u32,
+    tail: u32,
+    len: usize,
+    generation: u32,
+}
+
+impl<T> List<T> {
+    pub fn new() -> Self {
+        Self::with_capacity(0)
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            id: NEXT_LIST.fetch_add(1, Ordering::Relaxed),
+            entries: Vec::with_capacity(capacity),
+            free: NIL,
+            head: NIL,
+            tail: NIL,
+            len: 0,
+            generation: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Number of slots, used and free.
+    pub fn slots(&self) -> usize {
+        self.entries.len()
+    }
+
+    fn node(&self, i: u32) -> &Node<T> {
+        match &self.entries[i as usize].slot {
+            Slot::Used(node) => node,
+            Slot::Free(_) => unreachable!("linked slots are used"),
+        }
+    }
+
+    fn node_mut(&mut self, i: u32) -> &mut Node<T> {
+        match &mut self.entries[i as usize].slot {
+            Slot::Used(node) => node,
+            Slot::Free(_) => unreachable!("linked slots are used"),
+        }
+    }
+
+    fn handle(&self, i: u32) -> Handle {
+        Handle {
+            list: self.id,
+            index: i,
+            generation: self.entries[i as usize].generation,
+        }
+    }
+
+    fn to_handle(&self, i: u32) -> Option<Handle> {
+        (i != NIL).then(|| self.handle(i))
+    }
+
+    /// Returns the slot of `h`, if `h` refers to a node of this list.
+    fn resolve(&self, h: Handle) -> Result<u32, Error> {
+        if h.list != self.id {
+            return Err(anyhow!("handle of another list"));
+        }
+
+        match self.entries.get(h.index as usize) {
+            Some(Entry {
+                generation,
+                slot: Slot::Used(_),
+            }) if *generation == h.generation => Ok(h.index),
+            _ => Err(anyhow!("stale handle")),
+        }
+    }
+
+    /// # Panics
+    /// If the list has `u32::MAX` slots.
+    fn alloc(&mut self, node: Node<T>) -> u32 {
+        let generation = self.generation;
+        self.generation = self.generation.wrapping_add(1);
+        let entry = Entry {
+            generation,
+            slot: Slot::Used(node),
+        };
+
+        if self.free == NIL {
+            assert!(self.entries.len() < NIL as usize, "arena is full");
+            self.entries.push(entry);
+            return (self.entries.len() - 1) as u32;
+        }
+
+        let i = self.free;
+        let old = mem::replace(&mut self.entries[i as usize], entry);
+        self.free = match old.slot {
+            Slot::Free(next) => next,
+            Slot::Used(_) => unreachable!("the free list only holds free slots"),
+        };
+        i
+    }
+
+    /// Links a new node between `prev` and `next`, which have to be neighbours,
+    /// where `NIL` stands for the ends of the list.
+    fn link_between(&mut self, prev: u32, next: u32, val: T) -> Handle {
+        let i = self.alloc(Node { val, prev, next });
+        match prev {
+            NIL => self.head = i,
+            prev => self.node_mut(prev).next = i,
+        }
+        match next {
+            NIL => self.tail = i,
+            next => self.node_mut(next).prev = i,
+        }
+        self.len += 1;
+        self.handle(i)
+    }
+
+    /// Unlinks the node at slot `i` and puts the slot on the free list.
+    fn unlink(&mut self, i: u32) -> T {
+        let (prev, next) = {
+            let node = self.node(i);
+            (node.prev, node.next)
+        };
+        match prev {
+            NIL => self.head = next,
+            prev => self.node_mut(prev).next = next,
+        }
+        match next {
+            NIL => self.tail = prev,
+            next => self.node_mut(next).prev = prev,
+        }
+        self.len -= 1;
+
+        let old = mem::replace(&mut self.entries[i as usize].slot, Slot::Free(self.free));
+        self.free = i;
+        match old {
+            Slot::Used(node) => node.val,
+            Slot::Free(_) => unreachable!("linked slots are used"),
+        }
+    }
+
+    /// Walks from the closer end of the list.
+    fn walk_to_index(&self, i: usize) -> Result<u32, Error> {
+        if i >= self.len {
+            return Err(anyhow!("index {i} is out of bounds"));
+        }
+
+        if i < self.len / 2 {
+            let mut curr = self.head;
+            for _ in 0..i {
+                curr = self.node(curr).next;
+            }
+            Ok(curr)
+        } else {
+            let mut curr = self.tail;
+            for _ in i + 1..self.len {
+                curr = self.node(curr).prev;
+            }
+            Ok(curr)
+        }
+    }
+
+    fn walk_to_value(&self, val: &T) -> Result<u32, Error>
+    where
+        T: PartialEq,
+    {
+        let mut curr = self.head;
+        while curr != NIL {
+            let node = self.node(curr);
+            if node.val == *val {
+                return Ok(curr);
+            }
+            curr = node.next;
+        }
+
+        Err(anyhow!("value not found"))
+    }
+
+    pub fn front(&self) -> Option<Handle> {
+        self.to_handle(self.head)
+    }
+
+    pub fn back(&self) -> Option<Handle> {
+        self.to_handle(self.tail)
+    }
+
+    pub fn handle_at(&self, i: usize) -> Result<Handle, Error> {
+        let i = self.walk_to_index(i)?;
+        Ok(self.handle(i))
+    }
+
+    /// Returns the handle of the node after `h`, `None` at the end of the list.
+    pub fn next(&self, h: Handle) -> Result<Option<Handle>, Error> {
+        let i = self.resolve(h)?;
+        Ok(self.to_handle(self.node(i).next))
+    }
+
+    /// Returns the handle of the node before `h`, `None` at the start of the list.
+    pub fn prev(&self, h: Handle) -> Result<Option<Handle>, Error> {
+        let i = self.resolve(h)?;
+        Ok(self.to_handle(self.node(i).prev))
+    }
+
+    pub fn get(&self, i: usize) -> Result<&T, Error> {
+        let i = self.walk_to_index(i)?;
+        Ok(&self.node(i).val)
+    }
+
+    pub fn by_handle(&self, h: Handle) -> Result<&T, Error> {
+        let i = self.resolve(h)?;
+        Ok(&self.node(i).val)
+    }
+
+    pub fn by_handle_mut(&mut self, h: Handle) -> Result<&mut T, Error> {
+        let i = self.resolve(h)?;
+        Ok(&mut self.node_mut(i).val)
+    }
+
+    pub fn push_back(&mut self, val: T) -> Handle {
+        self.link_between(self.tail, NIL, val)
+    }
+
+    pub fn push_front(&mut self, val: T) -> Handle {
+        self.link_between(NIL, self.head, val)
+    }
+
+    pub fn insert_at(&mut self, i: usize, val: T) -> Result<Handle, Error> {
+        if i > self.len {
+            return Err(anyhow!("index {i} is out of bounds"));
+        }
+        if i == self.len {
+            return Ok(self.push_back(val));
+        }
+
+        let next = self.walk_to_index(i)?;
+        Ok(self.link_between(self.node(next).prev, next, val))
+    }
+
+    pub fn insert_after(&mut self, h: Handle, val: T) -> Result<Handle, Error> {
+        let prev = self.resolve(h)?;
+        Ok(self.link_between(prev, self.node(prev).next, val))
+    }
+
+    pub fn insert_before(&mut self, h: Handle, val: T) -> Result<Handle, Error> {
+        let next = self.resolve(h)?;
+        Ok(self.link_between(self.node(next).prev, next, val))
+    }
+
+    /// Removes the node of `h` in O(1).
+    pub fn remove(&mut self, h: Handle) -> Result<T, Error> {
+        let i = self.resolve(h)?;
+        Ok(self.unlink(i))
+    }
+
+    pub fn delete_at(&mut self, i: usize) -> Result<(), Error> {
+        let i = self.walk_to_index(i)?;
+        self.unlink(i);
+        Ok(())
+    }
+
+    pub fn delete(&mut self, val: &T) -> Result<(), Error>
+    where
+        T: PartialEq,
+    {
+        let i = self.walk_to_value(val)?;
+        self.unlink(i);
+        Ok(())
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        (self.head != NIL).then(|| self.unlink(self.head))
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        (self.tail != NIL).then(|| self.unlink(self.tail))
+    }
+
+    /// Moves all values of `other` to the back of the list, `other` is empty afterwards.
+    /// Unlike the pointer based lists, this moves every value into this arena.
+    /// Only if the list is empty the arenas are swapped instead, ids included,
+    /// so the handles of `other` then refer to this list.
+    pub fn append(&mut self, other: &mut List<T>) {
+        if self.is_empty() {
+            mem::swap(self, other);
+            return;
+        }
+
+        while let Some(val) = other.pop_front() {
+            self.push_back(val);
+        }
+    }
+
+    /// Splits the list at index `at` and returns the values from `at` on in a new arena.
+    pub fn split_off(&mut self, at: usize) -> Result<List<T>, Error> {
+        if at > self.len {
+            return Err(anyhow!("index {at} is out of bounds"));
+        }
+
+        let mut back = List::with_capacity(self.len - at);
+        while self.len > at {
+            let val = self.pop_back().expect("the list is longer than `at`");
+            back.push_front(val);
+        }
+        Ok(back)
+    }
+
+    /// Moves all values of `other` into the list, so that its first value ends up at index `i`.
+    pub fn splice_at(&mut self, i: usize, other: List<T>) -> Result<(), Error> {
+        if i > self.len {
+            return Err(anyhow!("index {i} is out of bounds"));
+        }
+
+        let mut next = match i {
+            i if i == self.len => NIL,
+            i => self.walk_to_index(i)?,
+        };
+        for val in other.into_iter().rev() {
+            let prev = match next {
+                NIL => self.tail,
+                next => self.node(next).prev,
+            };
+            next = self.link_between(prev, next, val).index;
+        }
+        Ok(())
+    }
+
+    /// Moves the nodes into the first `len` slots in list order and drops the free slots.
+    /// All handles become stale, the returned map tells the new handle for every old one.
+    pub fn compact(&mut self) -> HashMap<Handle, Handle> {
+        let old = mem::take(&mut self.entries);
+        let mut mapping = HashMap::with_capacity(self.len);
+        let mut entries = Vec::with_capacity(self.len);
+        let mut slots: Vec<Option<Entry<T>>> = old.into_iter().map(Some).collect();
+
+        let mut curr = self.head;
+        while curr != NIL {
+            let entry = slots[curr as usize]
+                .take()
+                .expect("every node is linked once");
+            let Slot::Used(node) = entry.slot else {
+                unreachable!("linked slots are used");
+            };
+
+            let i = entries.len() as u32;
+            let generation = self.generation;
+            self.generation = self.generation.wrapping_add(1);
+            mapping.insert(
+                Handle {
+                    list: self.id,
+                    index: curr,
+                    generation: entry.generation,
+                },
+                Handle {
+                    list: self.id,
+                    index: i,
+                    generation,
+                },
+            );
+            entries.push(Entry {
+                generation,
+                slot: Slot::Used(Node {
+                    val: node.val,
+                    prev: if i == 0 { NIL } else { i - 1 },
+                    next: if node.next == NIL { NIL } else { i + 1 },
+                }),
+            });
+            curr = node.next;
+        }
+
+        self.entries = entries;
+        self.free = NIL;
+        self.head = if self.len == 0 { NIL } else { 0 };
+        self.tail = if self.len == 0 {
+            NIL
+        } else {
+            self.len as u32 - 1
+        };
+        mapping
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            list: self,
+            front: self.head,
+            back: self.tail,
+            len: self.len,
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            front: self.head,
+            back: self.tail,
+            len: self.len,
+            entries: self.entries.as_mut_ptr(),
+            list: PhantomData,
+        }
+    }
+}
+
+impl<T> Default for List<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Iter walks from both ends until `len` nodes are handed out.
+pub struct Iter<'a, T> {
+    list: &'a List<T>,
+    front: u32,
+    back: u32,
+    len: usize,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        let node = self.list.node(self.front);
+        self.front = node.next;
+        Some(&node.val)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for Iter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        let node = self.list.node(self.back);
+        self.back = node.prev;
+        Some(&node.val)
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+/// IterMut reaches the nodes through a pointer to the entries,
+/// because a `&mut` to the whole `Vec` could only hand out one value at a time.
+pub struct IterMut<'a, T> {
+    front: u32,
+    back: u32,
+    len: usize,
+    entries: *mut Entry<T>,
+    list: PhantomData<&'a mut List<T>>,
+}
+
+impl<'a, T> IterMut<'a, T> {
+    /// # Safety
+    /// `i` has to be a linked slot that was not handed out before.
+    unsafe fn node(&self, i: u32) -> &'a mut Node<T> {
+        match &mut (*self.entries.add(i as usize)).slot {
+            Slot::Used(node) => node,
+            Slot::Free(_) => unreachable!("linked slots are used"),
+        }
+    }
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<&'a mut T> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        // SAFETY: the list is borrowed mutably for 'a and `len` makes sure
+        // that every node is handed out once
+        let node = unsafe { self.node(self.front) };
+        self.front = node.next;
+        Some(&mut node.val)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IterMut<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        // SAFETY: the list is borrowed mutably for 'a and `len` makes sure
+        // that every node is handed out once
+        let node = unsafe { self.node(self.back) };
+        self.back = node.prev;
+        Some(&mut node.val)
+    }
+}
+
+impl<T> ExactSizeIterator for IterMut<'_, T> {}
+
+pub struct IntoIter<T>(List<T>);
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.0.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0.len, Some(self.0.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<T> {
+        self.0.pop_back()
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> IntoIterator for List<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter(self)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a List<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut List<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+impl<T> Display for List<T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("[")?;
+        for (i, val) in self.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            f.write_fmt(format_args!("{}", val))?;
+        }
+        f.write_str("]")?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn list_equals<T>(l: &List<T>, v: &[T]) -> bool
+    where
+        T: PartialEq,
+    {
+        l.len == v.len() && l.iter().eq(v) && l.iter().rev().eq(v.iter().rev())
+    }
+
+    fn list_from(range: std::ops::Range<i32>) -> List<i32> {
+        let mut l = List::new();
+        for i in range {
+            l.push_back(i);
+        }
+        l
+    }
+
+    #[test]
+    fn it_works() {
+        let mut l = List::new();
+        l.push_back(1);
+        l.push_front(0);
+        l.push_back(3);
+        assert!(l.insert_at(2, 2).is_ok());
+        assert!(l.insert_at(5, 2).is_err());
+
+        assert!(list_equals(&l, &[0, 1, 2, 3]));
+        assert!(l.get(3).is_ok_and(|i| *i == 3));
+        assert!(l.get(4).is_err());
+
+        assert!(l.delete_at(1).is_ok());
+        assert!(l.delete(&3).is_ok());
+        assert!(l.delete(&3).is_err());
+        assert_eq!(l.to_string(), "[0, 2]");
+        assert_eq!(l.pop_back(), Some(2));
+        assert_eq!(l.pop_front(), Some(0));
+        assert_eq!(l.pop_front(), None);
+    }
+
+    #[test]
+    fn handles() {
+        let mut l = List::new();
+        let a = l.push_back("a".to_string());
+        let c = l.push_back("c".to_string());
+        let b = l.insert_before(c, "b".to_string()).unwrap();
+        l.insert_after(c, "d".to_string()).unwrap();
+
+        assert_eq!(l.front(), Some(a));
+        assert!(l.next(a).is_ok_and(|h| h == Some(b)));
+        assert!(l.prev(a).is_ok_and(|h| h.is_none()));
+        assert!(l.handle_at(2).is_ok_and(|h| h == c));
+
+        l.by_handle_mut(b).unwrap().push('!');
+        assert!(l.remove(a).is_ok_and(|s| s == "a"));
+        assert!(l.by_handle(c).is_ok_and(|s| s == "c"));
+        assert!(l.by_handle(b).is_ok_and(|s| s == "b!"));
+        assert_eq!(l.to_string(), "[b!, c, d]");
+    }
+
+    #[test]
+    fn stale_handles() {
+        let mut l = list_from(0..3);
+        let h = l.handle_at(1).unwrap();
+
+        assert!(l.remove(h).is_ok_and(|i| i == 1));
+        assert!(l.remove(h).is_err());
+        // the slot is reused, but the handle still does not match
+        let new = l.push_back(10);
+        assert_eq!(new.index, h.index);
+        assert!(l.by_handle(h).is_err());
+        assert!(l.insert_after(h, 0).is_err());
+        assert!(l.by_handle(new).is_ok_and(|i| *i == 10));
+
+        let other = list_from(0..3);
+        assert!(other
+            .by_handle(Handle {
+                list: other.id,
+                index: 7,
+                generation: 0
+            })
+            .is_err());
+    }
+
+    #[test]
+    fn handles_of_other_lists() {
+        let mut a = List::new();
+        let mut b = List::new();
+        let ha = a.push_back('a');
+        let hb = b.push_back('b');
+        assert_eq!((ha.index, ha.generation), (hb.index, hb.generation));
+
+        assert!(b.by_handle(ha).is_err());
+        assert!(b.insert_after(ha, 'x').is_err());
+        assert!(b.remove(ha).is_err());
+        assert!(b.by_handle(hb).is_ok_and(|c| *c == 'b'));
+        assert!(a.remove(ha).is_ok_and(|c| c == 'a'));
+
+        // appending to an empty list hands the arena over together with its handles
+        let mut c = List::new();
+        c.append(&mut b);
+        assert!(c.by_handle(hb).is_ok_and(|c| *c == 'b'));
+        assert!(b.by_handle(hb).is_err());
+    }
+
+    #[test]
+    fn reuses_slots() {
+        let mut l = list_from(0..8);
+        for _ in 0..100 {
+            let h = l.handle_at(3).unwrap();
+            let val = l.remove(h).unwrap();
+            l.push_front(val);
+        }
+
+        assert_eq!(l.slots(), 8);
+        assert_eq!(l.len(), 8);
+    }
+
+    #[test]
+    fn compact() {
+        let mut l = list_from(0..6);
+        let handles: Vec<_> = (0..6).map(|i| l.handle_at(i).unwrap()).collect();
+        l.remove(handles[0]).unwrap();
+        l.remove(handles[3]).unwrap();
+        let moved = l.remove(handles[5]).unwrap();
+        l.push_front(moved);
+        assert_eq!(l.slots(), 6);
+
+        let old = l.front().unwrap();
+        let mapping = l.compact();
+        assert_eq!(l.slots(), 4);
+        assert_eq!(mapping.len(), 4);
+        assert!(list_equals(&l, &[5, 1, 2, 4]));
+        assert!(l.by_handle(old).is_err());
+        assert_eq!(l.front(), Some(mapping[&old]));
+        assert!(l.by_handle(mapping[&handles[4]]).is_ok_and(|i| *i == 4));
+        for (i, h) in [1, 2].map(|i| mapping[&handles[i]]).iter().enumerate() {
+            assert_eq!(h.index as usize, i + 1);
+        }
+
+        l.push_back(6);
+        assert!(list_equals(&l, &[5, 1, 2, 4, 6]));
+        let mut empty = List::<i32>::new();
+        assert!(empty.compact().is_empty());
+        empty.push_back(0);
+        assert!(list_equals(&empty, &[0]));
+    }
+
+    #[test]
+    fn iterators() {
+        let mut l = List::new();
+        for i in 0..4 {
+            l.push_back(i.to_string());
+        }
+
+        for val in l.iter_mut().rev().take(2) {
+            val.push('!');
+        }
+        let mut iter = l.iter_mut();
+        iter.next().unwrap().push('?');
+        assert_eq!(iter.len(), 3);
+        assert!(l.iter().eq(["0?", "1", "2!", "3!"]));
+        assert_eq!(
+            l.into_iter().rev().collect::<Vec<_>>(),
+            ["3!", "2!", "1", "0?"]
+        );
+    }
+
+    #[test]
+    fn split_and_splice() {
+        let mut l = list_from(0..6);
+
+        let mut back = l.split_off(4).unwrap();
+        assert!(list_equals(&l, &[0, 1, 2, 3]));
+        assert!(list_equals(&back, &[4, 5]));
+        assert!(l.split_off(5).is_err());
+
+        l.splice_at(1, list_from(10..12)).unwrap();
+        l.splice_at(0, list_from(-1..0)).unwrap();
+        l.splice_at(7, List::new()).unwrap();
+        assert!(list_equals(&l, &[-1, 0, 10, 11, 1, 2, 3]));
+
+        l.append(&mut back);
+        assert!(back.is_empty());
+        assert!(list_equals(&l, &[-1, 0, 10, 11, 1, 2, 3, 4, 5]));
+        let mut empty = List::new();
+        empty.append(&mut l);
+        assert_eq!(empty.len(), 9);
+        assert!(l.split_off(0).is_ok_and(|l| l.is_empty()));
+    }
+
+    #[cfg(feature = "bench")]
+    mod bench {
+        use super::*;
+
+        extern crate test;
+        use test::{black_box, Bencher};
+
+        use crate::double_raw;
+
+        const BENCH_LEN: i64 = 1000;
+
+        #[bench]
+        fn bench_arena_push_iter(b: &mut Bencher) {
+            b.iter(|| {
+                let mut l = List::new();
+                for i in 0..BENCH_LEN {
+                    l.push_back(black_box(i));
+                }
+                l.iter().sum::<i64>()
+            })
+        }
+
+        #[bench]
+        fn bench_boxed_push_iter(b: &mut Bencher) {
+            b.iter(|| {
+                let mut l = double_raw::List::new();
+                for i in 0..BENCH_LEN {
+                    l.push_back(black_box(i));
+                }
+                l.iter().sum::<i64>()
+            })
+        }
+    }
+}
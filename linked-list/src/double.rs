@@ -1,30 +1,26 @@
-use std::{cell::RefCell, fmt::Display, rc::Rc};
+use std::{cell::RefCell, fmt::Display, marker::PhantomData, mem, rc::Rc};
 
 use anyhow::{anyhow, Error};
 
-type Link<T> = Option<Rc<RefCell<Node<T>>>>;
+pub(crate) type Link<T> = Option<Rc<RefCell<Node<T>>>>;
 fn new_link<T>(node: Node<T>) -> Link<T> {
     Some(Rc::new(RefCell::new(node)))
 }
 
-struct Node<T> {
-    val: T,
-    prev: Link<T>,
-    next: Link<T>,
+pub(crate) struct Node<T> {
+    pub(crate) val: T,
+    pub(crate) prev: Link<T>,
+    pub(crate) next: Link<T>,
 }
 
-struct List<T> {
-    len: usize,
-    head: Link<T>,
-    tail: Link<T>,
+pub struct List<T> {
+    pub(crate) len: usize,
+    pub(crate) head: Link<T>,
+    pub(crate) tail: Link<T>,
 }
 
-#[allow(dead_code)]
-impl<T> List<T>
-where
-    T: Copy + Eq,
-{
-    fn new() -> Self {
+impl<T> List<T> {
+    pub fn new() -> Self {
         Self {
             len: 0,
             head: None,
@@ -32,6 +28,140 @@ where
         }
     }
 
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns a cursor on the first node, or on the ghost position if the list is empty.
+    pub fn cursor_front(&self) -> Cursor<'_, T> {
+        Cursor {
+            list: self,
+            pos: Position::at_front(self),
+        }
+    }
+
+    /// Returns a cursor on the last node, or on the ghost position if the list is empty.
+    pub fn cursor_back(&self) -> Cursor<'_, T> {
+        Cursor {
+            list: self,
+            pos: Position::at_back(self),
+        }
+    }
+
+    pub fn cursor_front_mut(&mut self) -> CursorMut<'_, T> {
+        CursorMut {
+            pos: Position::at_front(self),
+            list: self,
+        }
+    }
+
+    pub fn cursor_back_mut(&mut self) -> CursorMut<'_, T> {
+        CursorMut {
+            pos: Position::at_back(self),
+            list: self,
+        }
+    }
+
+    /// Links a new node between `prev` and `next`, which have to be neighbours,
+    /// where `None` stands for the ends of the list.
+    fn link_between(&mut self, prev: Link<T>, next: Link<T>, val: T) {
+        let node = new_link(Node {
+            val,
+            prev: prev.clone(),
+            next: next.clone(),
+        });
+
+        match prev {
+            None => self.head = node.clone(),
+            Some(prev) => prev.as_ref().borrow_mut().next = node.clone(),
+        }
+        match next {
+            None => self.tail = node,
+            Some(next) => next.as_ref().borrow_mut().prev = node,
+        }
+        self.len += 1;
+    }
+
+    fn delete_node(&mut self, node: Rc<RefCell<Node<T>>>) {
+        let prev = node.as_ref().borrow_mut().prev.take();
+        let next = node.as_ref().borrow_mut().next.take();
+
+        match prev.clone() {
+            None => self.head = next.clone(),
+            Some(prev) => prev.as_ref().borrow_mut().next = next.clone(),
+        }
+        match next {
+            None => self.tail = prev,
+            Some(next) => next.as_ref().borrow_mut().prev = prev,
+        }
+        self.len -= 1;
+    }
+}
+
+/// NodeRef points at a node of a `List` and keeps it alive,
+/// so that an index like the one of `LruCache` can reach the node in O(1).
+pub(crate) struct NodeRef<T>(Rc<RefCell<Node<T>>>);
+
+impl<T> List<T> {
+    /// Links `node`, which must not be linked yet, in front of the head.
+    fn link_front(&mut self, node: &Rc<RefCell<Node<T>>>) {
+        let head = self.head.replace(node.clone());
+        match &head {
+            None => self.tail = Some(node.clone()),
+            Some(head) => head.as_ref().borrow_mut().prev = Some(node.clone()),
+        }
+        node.as_ref().borrow_mut().next = head;
+        self.len += 1;
+    }
+
+    pub(crate) fn push_front_node(&mut self, val: T) -> NodeRef<T> {
+        let node = Rc::new(RefCell::new(Node {
+            val,
+            prev: None,
+            next: None,
+        }));
+        self.link_front(&node);
+        NodeRef(node)
+    }
+
+    pub(crate) fn back_node(&self) -> Option<NodeRef<T>> {
+        self.tail.clone().map(NodeRef)
+    }
+
+    /// `node` has to be linked in this list.
+    pub(crate) fn move_to_front(&mut self, node: &NodeRef<T>) {
+        self.delete_node(node.0.clone());
+        self.link_front(&node.0);
+    }
+
+    /// `node` has to be linked in this list and be its only `NodeRef`.
+    pub(crate) fn remove_node(&mut self, node: NodeRef<T>) -> T {
+        self.delete_node(node.0.clone());
+
+        match Rc::try_unwrap(node.0) {
+            Ok(node) => node.into_inner().val,
+            Err(_) => unreachable!("an unlinked node is only referenced by its NodeRef"),
+        }
+    }
+
+    /// `node` has to be linked in this list.
+    pub(crate) fn node_val(&self, node: &NodeRef<T>) -> &T {
+        // SAFETY: the list keeps the node alive and is borrowed for as long as the reference
+        unsafe { val_ref(&node.0) }
+    }
+
+    /// `node` has to be linked in this list.
+    pub(crate) fn node_val_mut(&mut self, node: &NodeRef<T>) -> &mut T {
+        // SAFETY: the list keeps the node alive and is borrowed mutably for as long as the reference
+        unsafe { val_mut(&node.0) }
+    }
+}
+
+impl<T> List<T> {
     fn walk_to_index(&self, i: usize) -> Result<Rc<RefCell<Node<T>>>, Error> {
         let mut curr = self.head.clone();
 
@@ -50,14 +180,17 @@ where
         }
     }
 
-    fn walk_to_value(&self, val: T) -> Result<Rc<RefCell<Node<T>>>, Error> {
+    fn walk_to_value(&self, val: &T) -> Result<Rc<RefCell<Node<T>>>, Error>
+    where
+        T: PartialEq,
+    {
         let mut curr = self.head.clone();
 
         loop {
             match curr {
                 None => break,
                 Some(node) => {
-                    if node.borrow().val == val {
+                    if node.borrow().val == *val {
                         return Ok(node);
                     }
                     curr = node.borrow().next.clone();
@@ -68,13 +201,13 @@ where
         Err(anyhow!("value not found"))
     }
 
-    fn get(&self, i: usize) -> Result<T, Error> {
+    pub fn get(&self, i: usize) -> Result<&T, Error> {
         let node = self.walk_to_index(i)?;
-        let ret = node.borrow().val;
-        Ok(ret)
+        // SAFETY: the list is borrowed for as long as the reference
+        Ok(unsafe { val_ref(&node) })
     }
 
-    fn append(&mut self, val: T) {
+    pub fn push_back(&mut self, val: T) {
         let mut node = Node {
             val,
             next: None,
@@ -98,7 +231,7 @@ where
         self.len += 1;
     }
 
-    fn prepend(&mut self, val: T) {
+    pub fn push_front(&mut self, val: T) {
         let mut node = Node {
             val,
             next: None,
@@ -122,17 +255,17 @@ where
         self.len += 1;
     }
 
-    fn insert_at(&mut self, i: usize, val: T) -> Result<(), Error> {
+    pub fn insert_at(&mut self, i: usize, val: T) -> Result<(), Error> {
         if i > self.len {
             return Err(anyhow!("index {i} is out of bounds"));
         }
 
         if i == 0 {
-            self.prepend(val);
+            self.push_front(val);
             return Ok(());
         }
         if i == self.len {
-            self.append(val);
+            self.push_back(val);
             return Ok(());
         }
 
@@ -142,7 +275,7 @@ where
             .borrow_mut()
             .prev
             .take()
-            .ok_or(anyhow!("internal problem"))?
+            .ok_or_else(|| anyhow!("internal problem"))?
             .clone();
 
         let node = Some(Rc::new(RefCell::new(Node {
@@ -153,44 +286,518 @@ where
 
         prev.as_ref().borrow_mut().next = node.clone();
         next.as_ref().borrow_mut().prev = node;
+        self.len += 1;
         Ok(())
     }
 
-    fn delete_at(&mut self, i: usize) -> Result<(), Error> {
+    pub fn delete_at(&mut self, i: usize) -> Result<(), Error> {
         let node = self.walk_to_index(i)?;
         self.delete_node(node);
         Ok(())
     }
 
-    fn delete(&mut self, val: T) -> Result<(), Error> {
+    pub fn delete(&mut self, val: &T) -> Result<(), Error>
+    where
+        T: PartialEq,
+    {
         let node = self.walk_to_value(val)?;
         self.delete_node(node);
         Ok(())
     }
 
-    fn delete_node(&mut self, node: Rc<RefCell<Node<T>>>) {
-        let prev = node.as_ref().borrow_mut().prev.take();
-        let next = node.as_ref().borrow_mut().next.take();
+    /// Moves all nodes of `other` to the back of the list in O(1), `other` is empty afterwards.
+    pub fn append(&mut self, other: &mut List<T>) {
+        let Some(tail) = self.tail.as_ref() else {
+            mem::swap(self, other);
+            return;
+        };
+        let Some(head) = other.head.take() else {
+            return;
+        };
 
-        match prev.clone() {
-            None => self.head = next.clone(),
-            Some(prev) => prev.as_ref().borrow_mut().next = next.clone(),
+        head.as_ref().borrow_mut().prev = Some(tail.clone());
+        tail.as_ref().borrow_mut().next = Some(head);
+        self.tail = other.tail.take();
+        self.len += mem::take(&mut other.len);
+    }
+
+    /// Splits the list at index `at` and returns the nodes from `at` on.
+    /// Finding the node is O(n), the split itself is O(1).
+    pub fn split_off(&mut self, at: usize) -> Result<List<T>, Error> {
+        if at > self.len {
+            return Err(anyhow!("index {at} is out of bounds"));
         }
-        match next {
-            None => self.tail = prev,
-            Some(next) => next.as_ref().borrow_mut().prev = prev,
+        if at == self.len {
+            return Ok(List::new());
         }
+
+        let node = self.walk_to_index(at)?;
+        let mut cursor = CursorMut {
+            pos: Position {
+                curr: Some(node),
+                index: at,
+            },
+            list: self,
+        };
+        let front = cursor.split_before();
+        Ok(mem::replace(self, front))
+    }
+
+    /// Moves all nodes of `other` into the list, so that its head ends up at index `i`.
+    /// Finding the node is O(n), relinking is O(1).
+    pub fn splice_at(&mut self, i: usize, mut other: List<T>) -> Result<(), Error> {
+        if i > self.len {
+            return Err(anyhow!("index {i} is out of bounds"));
+        }
+        if i == self.len {
+            self.append(&mut other);
+            return Ok(());
+        }
+        let (Some(head), Some(tail)) = (other.head.take(), other.tail.take()) else {
+            return Ok(());
+        };
+
+        let next = self.walk_to_index(i)?;
+        let prev = next.as_ref().borrow_mut().prev.replace(tail.clone());
+        match &prev {
+            None => self.head = Some(head.clone()),
+            Some(prev) => prev.as_ref().borrow_mut().next = Some(head.clone()),
+        }
+        head.as_ref().borrow_mut().prev = prev;
+        tail.as_ref().borrow_mut().next = Some(next);
+        self.len += mem::take(&mut other.len);
+        Ok(())
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.cursor_front_mut().remove_current()
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        self.cursor_back_mut().remove_current()
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            front: self.head.clone(),
+            back: self.tail.clone(),
+            len: self.len,
+            list: PhantomData,
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            front: self.head.clone(),
+            back: self.tail.clone(),
+            len: self.len,
+            list: PhantomData,
+        }
+    }
+}
+
+impl<T> Default for List<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Unlinks the nodes one by one. Neighbours point at each other,
+/// so the nodes would never be freed otherwise.
+impl<T> Drop for List<T> {
+    fn drop(&mut self) {
+        self.tail = None;
+        let mut link = self.head.take();
+        while let Some(node) = link {
+            let mut node = node.as_ref().borrow_mut();
+            node.prev = None;
+            link = node.next.take();
+        }
+    }
+}
+
+/// Iter walks from both ends until `len` nodes are handed out.
+pub struct Iter<'a, T> {
+    front: Link<T>,
+    back: Link<T>,
+    len: usize,
+    list: PhantomData<&'a List<T>>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        if self.len == 0 {
+            return None;
+        }
+        let node = self.front.take()?;
+        self.front = node.borrow().next.clone();
         self.len -= 1;
+        // SAFETY: the list is borrowed for 'a
+        Some(unsafe { val_ref(&node) })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
     }
 }
 
-impl<T> Display for Node<T>
-where
-    T: Display,
-{
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_fmt(format_args!("{}", self.val))?;
-        Ok(())
+impl<T> DoubleEndedIterator for Iter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        let node = self.back.take()?;
+        self.back = node.borrow().prev.clone();
+        self.len -= 1;
+        // SAFETY: the list is borrowed for 'a
+        Some(unsafe { val_ref(&node) })
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+pub struct IterMut<'a, T> {
+    front: Link<T>,
+    back: Link<T>,
+    len: usize,
+    list: PhantomData<&'a mut List<T>>,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<&'a mut T> {
+        if self.len == 0 {
+            return None;
+        }
+        let node = self.front.take()?;
+        self.front = node.borrow().next.clone();
+        self.len -= 1;
+        // SAFETY: the list is borrowed mutably for 'a and `len` makes sure
+        // that every node is handed out once
+        Some(unsafe { val_mut(&node) })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IterMut<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        let node = self.back.take()?;
+        self.back = node.borrow().prev.clone();
+        self.len -= 1;
+        // SAFETY: the list is borrowed mutably for 'a and `len` makes sure
+        // that every node is handed out once
+        Some(unsafe { val_mut(&node) })
+    }
+}
+
+impl<T> ExactSizeIterator for IterMut<'_, T> {}
+
+pub struct IntoIter<T>(List<T>);
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.0.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0.len, Some(self.0.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<T> {
+        self.0.pop_back()
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> IntoIterator for List<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter(self)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a List<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut List<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+/// Position of a cursor. `curr` is `None` on the ghost position between the tail
+/// and the head, its index is then the length of the list.
+struct Position<T> {
+    curr: Link<T>,
+    index: usize,
+}
+
+impl<T> Position<T> {
+    fn at_front(list: &List<T>) -> Self {
+        Self {
+            curr: list.head.clone(),
+            index: 0,
+        }
+    }
+
+    fn at_back(list: &List<T>) -> Self {
+        Self {
+            curr: list.tail.clone(),
+            index: list.len.saturating_sub(1),
+        }
+    }
+
+    fn index(&self) -> Option<usize> {
+        self.curr.as_ref().map(|_| self.index)
+    }
+
+    fn move_next(&mut self, list: &List<T>) {
+        match self.curr.take() {
+            None => {
+                self.curr = list.head.clone();
+                self.index = 0;
+            }
+            Some(node) => {
+                self.curr = node.borrow().next.clone();
+                self.index += 1;
+            }
+        }
+    }
+
+    fn move_prev(&mut self, list: &List<T>) {
+        match self.curr.take() {
+            None => {
+                self.curr = list.tail.clone();
+                self.index = list.len.saturating_sub(1);
+            }
+            Some(node) => {
+                self.curr = node.borrow().prev.clone();
+                self.index = match self.curr {
+                    None => list.len,
+                    Some(_) => self.index - 1,
+                };
+            }
+        }
+    }
+
+    fn next(&self, list: &List<T>) -> Link<T> {
+        match &self.curr {
+            None => list.head.clone(),
+            Some(node) => node.borrow().next.clone(),
+        }
+    }
+
+    fn prev(&self, list: &List<T>) -> Link<T> {
+        match &self.curr {
+            None => list.tail.clone(),
+            Some(node) => node.borrow().prev.clone(),
+        }
+    }
+}
+
+/// Returns a reference to the value of `node` that is not tracked by the `RefCell`.
+///
+/// # Safety
+/// The list has to keep the node alive for `'a`, and no `RefCell` borrow of the
+/// value may happen while the reference is used. Both hold while the list is borrowed,
+/// because values are only borrowed by methods that take the list.
+pub(crate) unsafe fn val_ref<'a, T>(node: &Rc<RefCell<Node<T>>>) -> &'a T {
+    &(*node.as_ptr()).val
+}
+
+/// Returns a mutable reference to the value of `node`, see `val_ref`.
+///
+/// # Safety
+/// Like `val_ref`, and the list has to be borrowed mutably for `'a`.
+unsafe fn val_mut<'a, T>(node: &Rc<RefCell<Node<T>>>) -> &'a mut T {
+    &mut (*node.as_ptr()).val
+}
+
+/// Cursor points at a node of a borrowed list, or at the ghost position
+/// between the tail and the head. Moving wraps around through the ghost position.
+pub struct Cursor<'a, T> {
+    list: &'a List<T>,
+    pos: Position<T>,
+}
+
+impl<'a, T> Cursor<'a, T> {
+    /// Index of the current node, `None` on the ghost position.
+    pub fn index(&self) -> Option<usize> {
+        self.pos.index()
+    }
+
+    pub fn move_next(&mut self) {
+        self.pos.move_next(self.list);
+    }
+
+    pub fn move_prev(&mut self) {
+        self.pos.move_prev(self.list);
+    }
+
+    pub fn current(&self) -> Option<&'a T> {
+        // SAFETY: the list is borrowed for 'a
+        self.pos.curr.as_ref().map(|node| unsafe { val_ref(node) })
+    }
+
+    pub fn peek_next(&self) -> Option<&'a T> {
+        // SAFETY: the list is borrowed for 'a
+        self.pos
+            .next(self.list)
+            .map(|node| unsafe { val_ref(&node) })
+    }
+
+    pub fn peek_prev(&self) -> Option<&'a T> {
+        // SAFETY: the list is borrowed for 'a
+        self.pos
+            .prev(self.list)
+            .map(|node| unsafe { val_ref(&node) })
+    }
+}
+
+/// CursorMut is a cursor that can also change the list at its position in O(1).
+pub struct CursorMut<'a, T> {
+    list: &'a mut List<T>,
+    pos: Position<T>,
+}
+
+impl<T> CursorMut<'_, T> {
+    /// Index of the current node, `None` on the ghost position.
+    pub fn index(&self) -> Option<usize> {
+        self.pos.index()
+    }
+
+    pub fn move_next(&mut self) {
+        self.pos.move_next(self.list);
+    }
+
+    pub fn move_prev(&mut self) {
+        self.pos.move_prev(self.list);
+    }
+
+    pub fn current(&mut self) -> Option<&mut T> {
+        // SAFETY: the list is borrowed mutably for as long as the cursor
+        self.pos.curr.as_ref().map(|node| unsafe { val_mut(node) })
+    }
+
+    pub fn peek_next(&mut self) -> Option<&mut T> {
+        // SAFETY: the list is borrowed mutably for as long as the cursor
+        self.pos
+            .next(self.list)
+            .map(|node| unsafe { val_mut(&node) })
+    }
+
+    pub fn peek_prev(&mut self) -> Option<&mut T> {
+        // SAFETY: the list is borrowed mutably for as long as the cursor
+        self.pos
+            .prev(self.list)
+            .map(|node| unsafe { val_mut(&node) })
+    }
+
+    /// Returns a read-only cursor at the same position.
+    pub fn as_cursor(&self) -> Cursor<'_, T> {
+        Cursor {
+            list: self.list,
+            pos: Position {
+                curr: self.pos.curr.clone(),
+                index: self.pos.index,
+            },
+        }
+    }
+
+    /// Inserts `val` after the current node, or at the front on the ghost position.
+    pub fn insert_after(&mut self, val: T) {
+        let next = self.pos.next(self.list);
+        self.list.link_between(self.pos.curr.clone(), next, val);
+        if self.pos.curr.is_none() {
+            self.pos.index += 1;
+        }
+    }
+
+    /// Inserts `val` before the current node, or at the back on the ghost position.
+    pub fn insert_before(&mut self, val: T) {
+        let prev = self.pos.prev(self.list);
+        self.list.link_between(prev, self.pos.curr.clone(), val);
+        self.pos.index += 1;
+    }
+
+    /// Removes the current node and moves the cursor to the next one.
+    pub fn remove_current(&mut self) -> Option<T> {
+        let node = self.pos.curr.take()?;
+        self.pos.curr = node.borrow().next.clone();
+        self.list.delete_node(node.clone());
+
+        match Rc::try_unwrap(node) {
+            Ok(node) => Some(node.into_inner().val),
+            Err(_) => unreachable!("an unlinked node is only referenced by the cursor"),
+        }
+    }
+
+    /// Splits the list after the current node and returns the nodes after it.
+    /// On the ghost position the whole list is returned.
+    pub fn split_after(&mut self) -> List<T> {
+        let Some(curr) = self.pos.curr.clone() else {
+            self.pos.index = 0;
+            return mem::take(self.list);
+        };
+        let Some(next) = curr.as_ref().borrow_mut().next.take() else {
+            return List::new();
+        };
+        next.as_ref().borrow_mut().prev = None;
+
+        let len = self.list.len - self.pos.index - 1;
+        self.list.len -= len;
+        List {
+            len,
+            head: Some(next),
+            tail: self.list.tail.replace(curr),
+        }
+    }
+
+    /// Splits the list before the current node and returns the nodes before it.
+    /// On the ghost position the whole list is returned.
+    pub fn split_before(&mut self) -> List<T> {
+        let Some(curr) = self.pos.curr.clone() else {
+            self.pos.index = 0;
+            return mem::take(self.list);
+        };
+        let Some(prev) = curr.as_ref().borrow_mut().prev.take() else {
+            return List::new();
+        };
+        prev.as_ref().borrow_mut().next = None;
+
+        let len = self.pos.index;
+        self.list.len -= len;
+        self.pos.index = 0;
+        List {
+            len,
+            head: self.list.head.replace(curr),
+            tail: Some(prev),
+        }
     }
 }
 
@@ -199,24 +806,12 @@ where
     T: Display,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let mut curr = self.head.clone();
         f.write_str("[")?;
-
-        let mut i = 0;
-        loop {
-            match curr {
-                None => break,
-                Some(node) => {
-                    if i > 0 {
-                        f.write_str(", ")?;
-                    }
-                    i += 1;
-
-                    let node = node.borrow();
-                    f.write_fmt(format_args!("{}", node))?;
-                    curr = node.next.clone();
-                }
+        for (i, val) in self.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
             }
+            f.write_fmt(format_args!("{}", val))?;
         }
         f.write_str("]")?;
 
@@ -226,34 +821,31 @@ where
 
 #[cfg(test)]
 mod test {
+    use std::rc::Rc;
+
     use super::List;
 
     fn list_equals<T>(l: &List<T>, v: &[T]) -> bool
     where
-        T: Eq + Copy,
+        T: PartialEq,
     {
-        for i in 0..l.len {
-            if l.get(i).unwrap() != v[i] {
-                return false;
-            }
-        }
-        true
+        l.len == v.len() && l.iter().eq(v)
     }
 
     #[test]
-    fn append() {
+    fn push_back() {
         let mut l = List::<i32>::new();
-        l.append(0);
-        l.append(1);
+        l.push_back(0);
+        l.push_back(1);
 
         assert!(list_equals(&l, &[0, 1]));
     }
 
     #[test]
-    fn prepend() {
+    fn push_front() {
         let mut l = List::<i32>::new();
-        l.prepend(0);
-        l.prepend(1);
+        l.push_front(0);
+        l.push_front(1);
 
         assert!(list_equals(&l, &[1, 0]))
     }
@@ -261,11 +853,11 @@ mod test {
     #[test]
     fn insert() {
         let mut l = List::<i32>::new();
-        l.append(0);
-        l.append(1);
-        l.append(2);
-        l.append(3);
-        l.append(4);
+        l.push_back(0);
+        l.push_back(1);
+        l.push_back(2);
+        l.push_back(3);
+        l.push_back(4);
 
         l.insert_at(2, 10).unwrap();
 
@@ -276,11 +868,11 @@ mod test {
     #[test]
     fn delete_at() {
         let mut l = List::<i32>::new();
-        l.append(0);
-        l.append(1);
-        l.append(2);
-        l.append(3);
-        l.append(4);
+        l.push_back(0);
+        l.push_back(1);
+        l.push_back(2);
+        l.push_back(3);
+        l.push_back(4);
 
         l.delete_at(2).unwrap();
         println!("{l}");
@@ -298,14 +890,260 @@ mod test {
     #[test]
     fn delete() {
         let mut l = List::<i32>::new();
-        l.append(0);
-        l.append(1);
-        l.append(2);
-        l.append(3);
-        l.append(4);
+        l.push_back(0);
+        l.push_back(1);
+        l.push_back(2);
+        l.push_back(3);
+        l.push_back(4);
 
-        l.delete(2).unwrap();
+        l.delete(&2).unwrap();
         println!("{l}");
         assert!(list_equals(&l, &[0, 1, 3, 4]));
     }
+
+    #[test]
+    fn insert_len() {
+        let mut l = List::<i32>::new();
+        l.push_back(0);
+        l.push_back(2);
+        l.insert_at(1, 1).unwrap();
+
+        assert_eq!(l.len(), 3);
+        assert!(list_equals(&l, &[0, 1, 2]));
+    }
+
+    #[test]
+    fn cursor() {
+        let mut l = List::<i32>::new();
+        for i in 0..3 {
+            l.push_back(i);
+        }
+
+        let mut c = l.cursor_front();
+        assert_eq!(c.current(), Some(&0));
+        assert_eq!(c.peek_prev(), None);
+        c.move_next();
+        c.move_next();
+        assert_eq!(c.index(), Some(2));
+        assert_eq!(c.peek_next(), None);
+        c.move_next();
+        assert_eq!(c.index(), None);
+        assert_eq!(c.current(), None);
+        assert_eq!(c.peek_next(), Some(&0));
+        assert_eq!(c.peek_prev(), Some(&2));
+        c.move_next();
+        assert_eq!(c.current(), Some(&0));
+        c.move_prev();
+        c.move_prev();
+        assert_eq!(c.current(), Some(&2));
+        assert_eq!(c.index(), Some(2));
+
+        let empty = List::<i32>::new();
+        assert_eq!(empty.cursor_back().index(), None);
+    }
+
+    #[test]
+    fn cursor_mut() {
+        let mut l = List::<i32>::new();
+        l.push_back(1);
+        l.push_back(3);
+
+        let mut c = l.cursor_front_mut();
+        c.insert_before(0);
+        c.insert_after(2);
+        assert_eq!(c.index(), Some(1));
+        *c.current().unwrap() *= 10;
+        *c.peek_next().unwrap() *= 10;
+        c.move_prev();
+        c.move_prev();
+        c.insert_after(-1);
+        c.insert_before(4);
+        assert_eq!(c.index(), None);
+        assert_eq!(c.as_cursor().peek_prev(), Some(&4));
+        assert!(list_equals(&l, &[-1, 0, 10, 20, 3, 4]));
+        assert_eq!(l.len(), 6);
+        assert!(l.head.as_ref().unwrap().borrow().prev.is_none());
+        assert!(l.tail.as_ref().unwrap().borrow().next.is_none());
+    }
+
+    #[test]
+    fn remove_current() {
+        let mut l = List::<i32>::new();
+        for i in 0..4 {
+            l.push_back(i);
+        }
+
+        let mut c = l.cursor_front_mut();
+        assert_eq!(c.remove_current(), Some(0));
+        assert_eq!(c.index(), Some(0));
+        c.move_next();
+        assert_eq!(c.remove_current(), Some(2));
+        assert_eq!(c.current(), Some(&mut 3));
+        assert_eq!(c.remove_current(), Some(3));
+        assert_eq!(c.index(), None);
+        assert_eq!(c.remove_current(), None);
+        c.move_prev();
+        assert_eq!(c.current(), Some(&mut 1));
+        assert!(list_equals(&l, &[1]));
+        assert_eq!(l.len(), 1);
+        assert!(Rc::ptr_eq(
+            l.head.as_ref().unwrap(),
+            l.tail.as_ref().unwrap()
+        ));
+    }
+
+    #[test]
+    fn move_to_front() {
+        let mut l = List::<i32>::new();
+        for i in 0..5 {
+            l.push_back(i);
+        }
+
+        let mut c = l.cursor_back_mut();
+        c.move_prev();
+        let val = c.remove_current().unwrap();
+        l.push_front(val);
+        assert!(list_equals(&l, &[3, 0, 1, 2, 4]));
+    }
+
+    #[test]
+    fn split() {
+        let mut l = List::<i32>::new();
+        for i in 0..6 {
+            l.push_back(i);
+        }
+
+        let mut c = l.cursor_front_mut();
+        c.move_next();
+        c.move_next();
+        let after = c.split_after();
+        assert_eq!(c.index(), Some(2));
+        assert!(list_equals(&after, &[3, 4, 5]));
+        assert_eq!(after.len(), 3);
+
+        c.move_prev();
+        let before = c.split_before();
+        assert_eq!(c.index(), Some(0));
+        assert!(list_equals(&before, &[0]));
+        assert!(list_equals(&l, &[1, 2]));
+        assert_eq!(l.len(), 2);
+        assert!(l.head.as_ref().unwrap().borrow().prev.is_none());
+        assert!(before.tail.as_ref().unwrap().borrow().next.is_none());
+
+        let mut c = l.cursor_back_mut();
+        assert_eq!(c.split_after().len(), 0);
+        c.move_next();
+        let all = c.split_before();
+        assert_eq!(all.len(), 2);
+        assert!(l.is_empty());
+        assert!(l.head.is_none() && l.tail.is_none());
+    }
+
+    #[test]
+    fn iterators() {
+        let mut l = List::new();
+        for i in 0..4 {
+            l.push_back(i.to_string());
+        }
+
+        assert!(l.iter().rev().eq(["3", "2", "1", "0"]));
+        let mut iter = l.iter();
+        assert_eq!(iter.next().map(String::as_str), Some("0"));
+        assert_eq!(iter.next_back().map(String::as_str), Some("3"));
+        assert_eq!(iter.len(), 2);
+        assert!(iter.eq(["1", "2"]));
+
+        for val in l.iter_mut().rev().take(2) {
+            val.push('!');
+        }
+        assert_eq!(l.to_string(), "[0, 1, 2!, 3!]");
+        assert!(l.get(3).is_ok_and(|s| s == "3!"));
+        assert!(l.delete(&"1".to_string()).is_ok());
+
+        let mut iter = l.into_iter();
+        assert_eq!(iter.next_back(), Some("3!".to_string()));
+        assert_eq!(iter.collect::<Vec<_>>(), ["0", "2!"]);
+    }
+
+    #[test]
+    fn drop_frees_nodes() {
+        let counter = Rc::new(());
+        let mut l = List::new();
+        for _ in 0..5 {
+            l.push_back(Rc::clone(&counter));
+        }
+        l.pop_front();
+        l.pop_back();
+        assert_eq!(Rc::strong_count(&counter), 4);
+
+        drop(l);
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+
+    fn list_from(range: std::ops::Range<i32>) -> List<i32> {
+        let mut l = List::new();
+        for i in range {
+            l.push_back(i);
+        }
+        l
+    }
+
+    fn is_linked<T>(l: &List<T>) -> bool {
+        let forward = l.iter().count();
+        let mut backward = 0;
+        let mut curr = l.tail.clone();
+        while let Some(node) = curr {
+            backward += 1;
+            curr = node.borrow().prev.clone();
+        }
+        forward == l.len && backward == l.len
+    }
+
+    #[test]
+    fn append_list() {
+        let mut l = list_from(0..3);
+        let mut other = list_from(3..5);
+
+        l.append(&mut other);
+        assert!(list_equals(&l, &[0, 1, 2, 3, 4]));
+        assert!(other.is_empty() && other.head.is_none() && other.tail.is_none());
+        assert!(is_linked(&l));
+
+        let mut empty = List::new();
+        empty.append(&mut l);
+        assert!(list_equals(&empty, &[0, 1, 2, 3, 4]));
+        empty.append(&mut l);
+        assert_eq!(empty.len(), 5);
+    }
+
+    #[test]
+    fn split_off() {
+        let mut l = list_from(0..5);
+
+        let back = l.split_off(2).unwrap();
+        assert!(list_equals(&l, &[0, 1]));
+        assert!(list_equals(&back, &[2, 3, 4]));
+        assert!(is_linked(&l) && is_linked(&back));
+
+        assert!(l.split_off(3).is_err());
+        assert!(l.split_off(2).unwrap().is_empty());
+        let all = l.split_off(0).unwrap();
+        assert!(l.is_empty());
+        assert!(list_equals(&all, &[0, 1]));
+    }
+
+    #[test]
+    fn splice_at() {
+        let mut l = list_from(0..3);
+
+        l.splice_at(1, list_from(10..12)).unwrap();
+        assert!(list_equals(&l, &[0, 10, 11, 1, 2]));
+        l.splice_at(0, list_from(-2..0)).unwrap();
+        assert!(list_equals(&l, &[-2, -1, 0, 10, 11, 1, 2]));
+        l.splice_at(7, list_from(20..21)).unwrap();
+        l.splice_at(3, List::new()).unwrap();
+        assert!(list_equals(&l, &[-2, -1, 0, 10, 11, 1, 2, 20]));
+        assert!(is_linked(&l));
+        assert!(l.splice_at(9, List::new()).is_err());
+    }
 }
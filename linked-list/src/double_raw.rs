@@ -0,0 +1,914 @@
+use std::{fmt::Display, marker::PhantomData, mem, ptr::NonNull};
+
+use anyhow::{anyhow, Error};
+
+type Link<T> = Option<NonNull<Node<T>>>;
+
+struct Node<T> {
+    val: T,
+    prev: Link<T>,
+    next: Link<T>,
+}
+
+/// List has the same API as `double::List`, but the nodes are linked with raw pointers
+/// instead of `Rc<RefCell<_>>`, so there is no reference counting and no borrow flag.
+/// The list owns all of its nodes. They are allocated with `Box` when a value is inserted
+/// and freed when it is removed, every pointer in a node points to a node of the same list.
+pub struct List<T> {
+    len: usize,
+    head: Link<T>,
+    tail: Link<T>,
+    marker: PhantomData<Box<Node<T>>>,
+}
+
+impl<T> List<T> {
+    pub fn new() -> Self {
+        Self {
+            len: 0,
+            head: None,
+            tail: None,
+            marker: PhantomData,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns a cursor on the first node, or on the ghost position if the list is empty.
+    pub fn cursor_front(&self) -> Cursor<'_, T> {
+        Cursor {
+            list: self,
+            curr: self.head,
+            index: 0,
+        }
+    }
+
+    /// Returns a cursor on the last node, or on the ghost position if the list is empty.
+    pub fn cursor_back(&self) -> Cursor<'_, T> {
+        Cursor {
+            list: self,
+            curr: self.tail,
+            index: self.len.saturating_sub(1),
+        }
+    }
+
+    pub fn cursor_front_mut(&mut self) -> CursorMut<'_, T> {
+        CursorMut {
+            curr: self.head,
+            index: 0,
+            list: self,
+        }
+    }
+
+    pub fn cursor_back_mut(&mut self) -> CursorMut<'_, T> {
+        CursorMut {
+            curr: self.tail,
+            index: self.len.saturating_sub(1),
+            list: self,
+        }
+    }
+
+    /// Links a new node between `prev` and `next`, which have to be neighbours
+    /// in this list, where `None` stands for the ends of the list.
+    fn link_between(&mut self, prev: Link<T>, next: Link<T>, val: T) {
+        let node = NonNull::from(Box::leak(Box::new(Node { val, prev, next })));
+
+        // SAFETY: `prev` and `next` are nodes of this list
+        unsafe {
+            match prev {
+                None => self.head = Some(node),
+                Some(prev) => (*prev.as_ptr()).next = Some(node),
+            }
+            match next {
+                None => self.tail = Some(node),
+                Some(next) => (*next.as_ptr()).prev = Some(node),
+            }
+        }
+        self.len += 1;
+    }
+
+    /// Unlinks `node`, frees it and returns its value.
+    ///
+    /// # Safety
+    /// `node` has to be a node of this list.
+    unsafe fn unlink(&mut self, node: NonNull<Node<T>>) -> T {
+        let node = Box::from_raw(node.as_ptr());
+        match node.prev {
+            None => self.head = node.next,
+            Some(prev) => (*prev.as_ptr()).next = node.next,
+        }
+        match node.next {
+            None => self.tail = node.prev,
+            Some(next) => (*next.as_ptr()).prev = node.prev,
+        }
+        self.len -= 1;
+        node.val
+    }
+
+    /// Walks from the closer end of the list.
+    fn walk_to_index(&self, i: usize) -> Result<NonNull<Node<T>>, Error> {
+        if i >= self.len {
+            return Err(anyhow!("index {i} is out of bounds"));
+        }
+
+        // SAFETY: all links point to nodes of the list, and there are more than `i` nodes
+        unsafe {
+            if i < self.len / 2 {
+                let mut node = self.head.expect("the list is not empty");
+                for _ in 0..i {
+                    node = (*node.as_ptr()).next.expect("the index is in bounds");
+                }
+                Ok(node)
+            } else {
+                let mut node = self.tail.expect("the list is not empty");
+                for _ in i + 1..self.len {
+                    node = (*node.as_ptr()).prev.expect("the index is in bounds");
+                }
+                Ok(node)
+            }
+        }
+    }
+
+    fn walk_to_value(&self, val: &T) -> Result<NonNull<Node<T>>, Error>
+    where
+        T: PartialEq,
+    {
+        let mut curr = self.head;
+        while let Some(node) = curr {
+            // SAFETY: all links point to nodes of the list
+            let node_ref = unsafe { node.as_ref() };
+            if node_ref.val == *val {
+                return Ok(node);
+            }
+            curr = node_ref.next;
+        }
+
+        Err(anyhow!("value not found"))
+    }
+
+    pub fn get(&self, i: usize) -> Result<&T, Error> {
+        let node = self.walk_to_index(i)?;
+        // SAFETY: the node belongs to the list, which is borrowed for as long as the reference
+        Ok(unsafe { &(*node.as_ptr()).val })
+    }
+
+    pub fn push_back(&mut self, val: T) {
+        self.link_between(self.tail, None, val);
+    }
+
+    pub fn push_front(&mut self, val: T) {
+        self.link_between(None, self.head, val);
+    }
+
+    pub fn insert_at(&mut self, i: usize, val: T) -> Result<(), Error> {
+        if i > self.len {
+            return Err(anyhow!("index {i} is out of bounds"));
+        }
+        if i == self.len {
+            self.push_back(val);
+            return Ok(());
+        }
+
+        let next = self.walk_to_index(i)?;
+        // SAFETY: the node belongs to the list
+        let prev = unsafe { (*next.as_ptr()).prev };
+        self.link_between(prev, Some(next), val);
+        Ok(())
+    }
+
+    pub fn delete_at(&mut self, i: usize) -> Result<(), Error> {
+        let node = self.walk_to_index(i)?;
+        // SAFETY: the node belongs to the list
+        unsafe { self.unlink(node) };
+        Ok(())
+    }
+
+    pub fn delete(&mut self, val: &T) -> Result<(), Error>
+    where
+        T: PartialEq,
+    {
+        let node = self.walk_to_value(val)?;
+        // SAFETY: the node belongs to the list
+        unsafe { self.unlink(node) };
+        Ok(())
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        // SAFETY: the head belongs to the list
+        self.head.map(|node| unsafe { self.unlink(node) })
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        // SAFETY: the tail belongs to the list
+        self.tail.map(|node| unsafe { self.unlink(node) })
+    }
+
+    /// Moves all nodes of `other` to the back of the list in O(1), `other` is empty afterwards.
+    pub fn append(&mut self, other: &mut List<T>) {
+        let Some(tail) = self.tail else {
+            mem::swap(self, other);
+            return;
+        };
+        let Some(head) = other.head.take() else {
+            return;
+        };
+
+        // SAFETY: `tail` and `head` belong to the two lists, which become one
+        unsafe {
+            (*head.as_ptr()).prev = Some(tail);
+            (*tail.as_ptr()).next = Some(head);
+        }
+        self.tail = other.tail.take();
+        self.len += mem::take(&mut other.len);
+    }
+
+    /// Splits the list at index `at` and returns the nodes from `at` on.
+    /// Finding the node is O(n), the split itself is O(1).
+    pub fn split_off(&mut self, at: usize) -> Result<List<T>, Error> {
+        if at > self.len {
+            return Err(anyhow!("index {at} is out of bounds"));
+        }
+        if at == self.len {
+            return Ok(List::new());
+        }
+
+        let node = self.walk_to_index(at)?;
+        let mut cursor = CursorMut {
+            curr: Some(node),
+            index: at,
+            list: self,
+        };
+        let front = cursor.split_before();
+        Ok(mem::replace(self, front))
+    }
+
+    /// Moves all nodes of `other` into the list, so that its head ends up at index `i`.
+    /// Finding the node is O(n), relinking is O(1).
+    pub fn splice_at(&mut self, i: usize, mut other: List<T>) -> Result<(), Error> {
+        if i > self.len {
+            return Err(anyhow!("index {i} is out of bounds"));
+        }
+        if i == self.len {
+            self.append(&mut other);
+            return Ok(());
+        }
+        let (Some(head), Some(tail)) = (other.head.take(), other.tail.take()) else {
+            return Ok(());
+        };
+
+        let next = self.walk_to_index(i)?;
+        // SAFETY: `next` and its neighbour belong to this list, `head` and `tail` to `other`,
+        // which is left empty
+        unsafe {
+            let prev = (*next.as_ptr()).prev.replace(tail);
+            match prev {
+                None => self.head = Some(head),
+                Some(prev) => (*prev.as_ptr()).next = Some(head),
+            }
+            (*head.as_ptr()).prev = prev;
+            (*tail.as_ptr()).next = Some(next);
+        }
+        self.len += mem::take(&mut other.len);
+        Ok(())
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            front: self.head,
+            back: self.tail,
+            len: self.len,
+            list: PhantomData,
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            front: self.head,
+            back: self.tail,
+            len: self.len,
+            list: PhantomData,
+        }
+    }
+}
+
+impl<T> Default for List<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for List<T> {
+    fn drop(&mut self) {
+        while self.pop_front().is_some() {}
+    }
+}
+
+/// Iter walks from both ends until `len` nodes are handed out.
+pub struct Iter<'a, T> {
+    front: Link<T>,
+    back: Link<T>,
+    len: usize,
+    list: PhantomData<&'a List<T>>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        self.front.map(|node| {
+            // SAFETY: the list is borrowed for 'a
+            let node = unsafe { &*node.as_ptr() };
+            self.front = node.next;
+            &node.val
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for Iter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        self.back.map(|node| {
+            // SAFETY: the list is borrowed for 'a
+            let node = unsafe { &*node.as_ptr() };
+            self.back = node.prev;
+            &node.val
+        })
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+pub struct IterMut<'a, T> {
+    front: Link<T>,
+    back: Link<T>,
+    len: usize,
+    list: PhantomData<&'a mut List<T>>,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<&'a mut T> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        self.front.map(|node| {
+            // SAFETY: the list is borrowed mutably for 'a and `len` makes sure
+            // that every node is handed out once
+            unsafe {
+                self.front = (*node.as_ptr()).next;
+                &mut (*node.as_ptr()).val
+            }
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IterMut<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        self.back.map(|node| {
+            // SAFETY: the list is borrowed mutably for 'a and `len` makes sure
+            // that every node is handed out once
+            unsafe {
+                self.back = (*node.as_ptr()).prev;
+                &mut (*node.as_ptr()).val
+            }
+        })
+    }
+}
+
+impl<T> ExactSizeIterator for IterMut<'_, T> {}
+
+pub struct IntoIter<T>(List<T>);
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.0.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0.len, Some(self.0.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<T> {
+        self.0.pop_back()
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> IntoIterator for List<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter(self)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a List<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut List<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+/// Cursor points at a node of a borrowed list, or at the ghost position
+/// between the tail and the head. Moving wraps around through the ghost position.
+/// On the ghost position `curr` is `None` and `index` is the length of the list.
+pub struct Cursor<'a, T> {
+    list: &'a List<T>,
+    curr: Link<T>,
+    index: usize,
+}
+
+impl<'a, T> Cursor<'a, T> {
+    /// Index of the current node, `None` on the ghost position.
+    pub fn index(&self) -> Option<usize> {
+        self.curr.map(|_| self.index)
+    }
+
+    pub fn move_next(&mut self) {
+        (self.curr, self.index) = step_next(self.list, self.curr, self.index);
+    }
+
+    pub fn move_prev(&mut self) {
+        (self.curr, self.index) = step_prev(self.list, self.curr, self.index);
+    }
+
+    pub fn current(&self) -> Option<&'a T> {
+        // SAFETY: the list is borrowed for 'a
+        self.curr.map(|node| unsafe { &(*node.as_ptr()).val })
+    }
+
+    pub fn peek_next(&self) -> Option<&'a T> {
+        // SAFETY: the list is borrowed for 'a
+        next_of(self.list, self.curr).map(|node| unsafe { &(*node.as_ptr()).val })
+    }
+
+    pub fn peek_prev(&self) -> Option<&'a T> {
+        // SAFETY: the list is borrowed for 'a
+        prev_of(self.list, self.curr).map(|node| unsafe { &(*node.as_ptr()).val })
+    }
+}
+
+fn next_of<T>(list: &List<T>, curr: Link<T>) -> Link<T> {
+    match curr {
+        None => list.head,
+        // SAFETY: the cursor only points at nodes of the list
+        Some(node) => unsafe { (*node.as_ptr()).next },
+    }
+}
+
+fn prev_of<T>(list: &List<T>, curr: Link<T>) -> Link<T> {
+    match curr {
+        None => list.tail,
+        // SAFETY: the cursor only points at nodes of the list
+        Some(node) => unsafe { (*node.as_ptr()).prev },
+    }
+}
+
+fn step_next<T>(list: &List<T>, curr: Link<T>, index: usize) -> (Link<T>, usize) {
+    match curr {
+        None => (list.head, 0),
+        Some(_) => (next_of(list, curr), index + 1),
+    }
+}
+
+fn step_prev<T>(list: &List<T>, curr: Link<T>, index: usize) -> (Link<T>, usize) {
+    let prev = prev_of(list, curr);
+    match (curr, prev) {
+        (None, _) => (prev, list.len.saturating_sub(1)),
+        (Some(_), None) => (None, list.len),
+        (Some(_), Some(_)) => (prev, index - 1),
+    }
+}
+
+/// CursorMut is a cursor that can also change the list at its position in O(1).
+pub struct CursorMut<'a, T> {
+    list: &'a mut List<T>,
+    curr: Link<T>,
+    index: usize,
+}
+
+impl<T> CursorMut<'_, T> {
+    /// Index of the current node, `None` on the ghost position.
+    pub fn index(&self) -> Option<usize> {
+        self.curr.map(|_| self.index)
+    }
+
+    pub fn move_next(&mut self) {
+        (self.curr, self.index) = step_next(self.list, self.curr, self.index);
+    }
+
+    pub fn move_prev(&mut self) {
+        (self.curr, self.index) = step_prev(self.list, self.curr, self.index);
+    }
+
+    pub fn current(&mut self) -> Option<&mut T> {
+        // SAFETY: the list is borrowed mutably for as long as the cursor
+        self.curr.map(|node| unsafe { &mut (*node.as_ptr()).val })
+    }
+
+    pub fn peek_next(&mut self) -> Option<&mut T> {
+        // SAFETY: the list is borrowed mutably for as long as the cursor
+        next_of(self.list, self.curr).map(|node| unsafe { &mut (*node.as_ptr()).val })
+    }
+
+    pub fn peek_prev(&mut self) -> Option<&mut T> {
+        // SAFETY: the list is borrowed mutably for as long as the cursor
+        prev_of(self.list, self.curr).map(|node| unsafe { &mut (*node.as_ptr()).val })
+    }
+
+    /// Returns a read-only cursor at the same position.
+    pub fn as_cursor(&self) -> Cursor<'_, T> {
+        Cursor {
+            list: self.list,
+            curr: self.curr,
+            index: self.index,
+        }
+    }
+
+    /// Inserts `val` after the current node, or at the front on the ghost position.
+    pub fn insert_after(&mut self, val: T) {
+        let next = next_of(self.list, self.curr);
+        self.list.link_between(self.curr, next, val);
+        if self.curr.is_none() {
+            self.index += 1;
+        }
+    }
+
+    /// Inserts `val` before the current node, or at the back on the ghost position.
+    pub fn insert_before(&mut self, val: T) {
+        let prev = prev_of(self.list, self.curr);
+        self.list.link_between(prev, self.curr, val);
+        self.index += 1;
+    }
+
+    /// Removes the current node and moves the cursor to the next one.
+    pub fn remove_current(&mut self) -> Option<T> {
+        let node = self.curr?;
+        self.curr = next_of(self.list, self.curr);
+        // SAFETY: the cursor only points at nodes of the list
+        Some(unsafe { self.list.unlink(node) })
+    }
+
+    /// Splits the list after the current node and returns the nodes after it.
+    /// On the ghost position the whole list is returned.
+    pub fn split_after(&mut self) -> List<T> {
+        let Some(curr) = self.curr else {
+            self.index = 0;
+            return mem::take(self.list);
+        };
+        // SAFETY: the cursor only points at nodes of the list, the nodes after `curr`
+        // are moved to the new list
+        let Some(next) = (unsafe { (*curr.as_ptr()).next.take() }) else {
+            return List::new();
+        };
+        unsafe { (*next.as_ptr()).prev = None };
+
+        let len = self.list.len - self.index - 1;
+        self.list.len -= len;
+        List {
+            len,
+            head: Some(next),
+            tail: self.list.tail.replace(curr),
+            marker: PhantomData,
+        }
+    }
+
+    /// Splits the list before the current node and returns the nodes before it.
+    /// On the ghost position the whole list is returned.
+    pub fn split_before(&mut self) -> List<T> {
+        let Some(curr) = self.curr else {
+            self.index = 0;
+            return mem::take(self.list);
+        };
+        // SAFETY: the cursor only points at nodes of the list, the nodes before `curr`
+        // are moved to the new list
+        let Some(prev) = (unsafe { (*curr.as_ptr()).prev.take() }) else {
+            return List::new();
+        };
+        unsafe { (*prev.as_ptr()).next = None };
+
+        let len = self.index;
+        self.list.len -= len;
+        self.index = 0;
+        List {
+            len,
+            head: self.list.head.replace(curr),
+            tail: Some(prev),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Display for List<T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("[")?;
+        for (i, val) in self.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            f.write_fmt(format_args!("{}", val))?;
+        }
+        f.write_str("]")?;
+
+        Ok(())
+    }
+}
+
+/// The tests only use a few nodes, so that they also run quickly under Miri,
+/// which checks the pointer accesses and that no node is leaked.
+#[cfg(test)]
+mod test {
+    use std::rc::Rc;
+
+    use super::List;
+
+    fn list_equals<T>(l: &List<T>, v: &[T]) -> bool
+    where
+        T: PartialEq,
+    {
+        l.len == v.len() && l.iter().eq(v) && l.iter().rev().eq(v.iter().rev())
+    }
+
+    fn list_from(range: std::ops::Range<i32>) -> List<i32> {
+        let mut l = List::new();
+        for i in range {
+            l.push_back(i);
+        }
+        l
+    }
+
+    #[test]
+    fn push() {
+        let mut l = List::new();
+        l.push_back(1);
+        l.push_front(0);
+        l.push_back(2);
+
+        assert!(list_equals(&l, &[0, 1, 2]));
+        assert!(l.get(2).is_ok_and(|i| *i == 2));
+        assert!(l.get(3).is_err());
+    }
+
+    #[test]
+    fn insert() {
+        let mut l = list_from(0..5);
+
+        l.insert_at(2, 10).unwrap();
+        l.insert_at(0, -1).unwrap();
+        l.insert_at(7, 20).unwrap();
+        assert!(l.insert_at(9, 0).is_err());
+        assert_eq!(l.to_string(), "[-1, 0, 1, 10, 2, 3, 4, 20]");
+        assert!(list_equals(&l, &[-1, 0, 1, 10, 2, 3, 4, 20]));
+    }
+
+    #[test]
+    fn delete() {
+        let mut l = list_from(0..5);
+
+        l.delete_at(2).unwrap();
+        assert!(list_equals(&l, &[0, 1, 3, 4]));
+        l.delete_at(0).unwrap();
+        l.delete_at(2).unwrap();
+        assert!(list_equals(&l, &[1, 3]));
+        assert!(l.delete_at(2).is_err());
+
+        l.delete(&3).unwrap();
+        assert!(l.delete(&3).is_err());
+        assert!(list_equals(&l, &[1]));
+        assert_eq!(l.pop_back(), Some(1));
+        assert_eq!(l.pop_front(), None);
+    }
+
+    #[test]
+    fn owned_values() {
+        let mut l = List::new();
+        for i in 0..4 {
+            l.push_back(i.to_string());
+        }
+
+        for val in l.iter_mut().rev().take(2) {
+            val.push('!');
+        }
+        assert!(l.delete(&"1".to_string()).is_ok());
+        assert!(l.get(1).is_ok_and(|s| s == "2!"));
+
+        let mut iter = l.into_iter();
+        assert_eq!(iter.next_back(), Some("3!".to_string()));
+        assert_eq!(iter.len(), 2);
+        assert_eq!(iter.next(), Some("0".to_string()));
+    }
+
+    #[test]
+    fn drops_values() {
+        let counter = Rc::new(());
+        let mut l = List::new();
+        for _ in 0..5 {
+            l.push_back(Rc::clone(&counter));
+        }
+        l.pop_front();
+        l.delete_at(2).unwrap();
+        assert_eq!(Rc::strong_count(&counter), 4);
+
+        drop(l);
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+
+    #[test]
+    fn cursor() {
+        let l = list_from(0..3);
+
+        let mut c = l.cursor_front();
+        assert_eq!(c.current(), Some(&0));
+        assert_eq!(c.peek_prev(), None);
+        c.move_next();
+        c.move_next();
+        assert_eq!(c.index(), Some(2));
+        c.move_next();
+        assert_eq!(c.index(), None);
+        assert_eq!(c.peek_next(), Some(&0));
+        assert_eq!(c.peek_prev(), Some(&2));
+        c.move_next();
+        c.move_prev();
+        c.move_prev();
+        assert_eq!(c.current(), Some(&2));
+        assert_eq!(c.index(), Some(2));
+
+        let empty = List::<i32>::new();
+        assert_eq!(empty.cursor_back().index(), None);
+    }
+
+    #[test]
+    fn cursor_mut() {
+        let mut l = list_from(1..2);
+        l.push_back(3);
+
+        let mut c = l.cursor_front_mut();
+        c.insert_before(0);
+        c.insert_after(2);
+        assert_eq!(c.index(), Some(1));
+        *c.current().unwrap() *= 10;
+        *c.peek_next().unwrap() *= 10;
+        c.move_prev();
+        c.move_prev();
+        c.insert_after(-1);
+        c.insert_before(4);
+        assert_eq!(c.index(), None);
+        assert_eq!(c.as_cursor().peek_prev(), Some(&4));
+        assert!(list_equals(&l, &[-1, 0, 10, 20, 3, 4]));
+
+        let mut c = l.cursor_front_mut();
+        c.move_next();
+        assert_eq!(c.remove_current(), Some(0));
+        assert_eq!(c.current(), Some(&mut 10));
+        let mut c = l.cursor_back_mut();
+        assert_eq!(c.remove_current(), Some(4));
+        assert_eq!(c.index(), None);
+        assert!(list_equals(&l, &[-1, 10, 20, 3]));
+    }
+
+    #[test]
+    fn split_and_splice() {
+        let mut l = list_from(0..6);
+
+        let mut c = l.cursor_front_mut();
+        c.move_next();
+        c.move_next();
+        let mut after = c.split_after();
+        c.move_prev();
+        let before = c.split_before();
+        assert!(list_equals(&before, &[0]));
+        assert!(list_equals(&l, &[1, 2]));
+        assert!(list_equals(&after, &[3, 4, 5]));
+
+        l.append(&mut after);
+        assert!(after.is_empty());
+        l.splice_at(0, before).unwrap();
+        l.splice_at(2, list_from(10..12)).unwrap();
+        assert!(list_equals(&l, &[0, 1, 10, 11, 2, 3, 4, 5]));
+
+        let back = l.split_off(5).unwrap();
+        assert!(list_equals(&back, &[3, 4, 5]));
+        assert!(list_equals(&l, &[0, 1, 10, 11, 2]));
+        assert!(l.split_off(6).is_err());
+        assert!(l.split_off(0).is_ok_and(|all| all.len() == 5));
+        assert!(l.is_empty());
+    }
+
+    #[cfg(feature = "bench")]
+    mod bench {
+        use super::*;
+
+        extern crate test;
+        use test::{black_box, Bencher};
+
+        use crate::double;
+
+        const BENCH_LEN: i64 = 1000;
+
+        #[bench]
+        fn bench_raw_push_iter(b: &mut Bencher) {
+            b.iter(|| {
+                let mut l = List::new();
+                for i in 0..BENCH_LEN {
+                    l.push_back(black_box(i));
+                }
+                l.iter().sum::<i64>()
+            })
+        }
+
+        #[bench]
+        fn bench_rc_push_iter(b: &mut Bencher) {
+            b.iter(|| {
+                let mut l = double::List::new();
+                for i in 0..BENCH_LEN {
+                    l.push_back(black_box(i));
+                }
+                l.iter().sum::<i64>()
+            })
+        }
+
+        #[bench]
+        fn bench_raw_cursor_remove(b: &mut Bencher) {
+            b.iter(|| {
+                let mut l = List::new();
+                for i in 0..BENCH_LEN {
+                    l.push_back(black_box(i));
+                }
+                let mut c = l.cursor_front_mut();
+                while let Some(val) = c.current() {
+                    if *val % 2 == 0 {
+                        c.remove_current();
+                    } else {
+                        c.move_next();
+                    }
+                }
+                l
+            })
+        }
+
+        #[bench]
+        fn bench_rc_cursor_remove(b: &mut Bencher) {
+            b.iter(|| {
+                let mut l = double::List::new();
+                for i in 0..BENCH_LEN {
+                    l.push_back(black_box(i));
+                }
+                let mut c = l.cursor_front_mut();
+                while let Some(val) = c.current() {
+                    if *val % 2 == 0 {
+                        c.remove_current();
+                    } else {
+                        c.move_next();
+                    }
+                }
+                l
+            })
+        }
+    }
+}
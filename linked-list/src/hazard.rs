@@ -0,0 +1,317 @@
+//! Hazard pointers for the lock-free lists.
+//!
+//! A thread that wants to read through a shared pointer publishes it in a hazard record first.
+//! Nodes that were unlinked are retired instead of freed, and a retired node is only freed
+//! by a scan that finds it in no hazard record.
+
+use std::{
+    ptr,
+    sync::atomic::{self, AtomicBool, AtomicPtr, AtomicUsize, Ordering},
+};
+
+/// Scan the retired nodes after this many retires.
+const SCAN_EVERY: usize = 64;
+
+struct Record {
+    hazard: AtomicPtr<u8>,
+    active: AtomicBool,
+    /// Records are never unlinked before the domain is dropped, so this never changes.
+    next: *mut Record,
+}
+
+struct Retired {
+    ptr: *mut u8,
+    delete: unsafe fn(*mut u8),
+    next: *mut Retired,
+}
+
+/// # Safety
+/// `ptr` has to come from `Box::<T>::into_raw` and must not be used anymore.
+unsafe fn delete<T>(ptr: *mut u8) {
+    drop(Box::from_raw(ptr as *mut T));
+}
+
+/// Domain holds the hazard records and retired nodes of one data structure.
+/// Both are kept in lock-free stacks, so neither `guard` nor `retire` ever blocks.
+pub(crate) struct Domain {
+    records: AtomicPtr<Record>,
+    retired: AtomicPtr<Retired>,
+    retired_count: AtomicUsize,
+}
+
+// SAFETY: the raw pointers are only shared through atomics,
+// and the retired nodes are only freed by whoever took them off the list
+unsafe impl Send for Domain {}
+unsafe impl Sync for Domain {}
+
+impl Domain {
+    pub(crate) fn new() -> Self {
+        Self {
+            records: AtomicPtr::new(ptr::null_mut()),
+            retired: AtomicPtr::new(ptr::null_mut()),
+            retired_count: AtomicUsize::new(0),
+        }
+    }
+
+    /// Returns a guard with an unused hazard record, the record is allocated if none is free.
+    pub(crate) fn guard(&self) -> Guard<'_> {
+        let mut curr = self.records.load(Ordering::Acquire);
+        while !curr.is_null() {
+            // SAFETY: records live as long as the domain
+            let record = unsafe { &*curr };
+            if !record.active.load(Ordering::Relaxed)
+                && record
+                    .active
+                    .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+                    .is_ok()
+            {
+                return Guard { record };
+            }
+            curr = record.next;
+        }
+
+        let record = Box::into_raw(Box::new(Record {
+            hazard: AtomicPtr::new(ptr::null_mut()),
+            active: AtomicBool::new(true),
+            next: ptr::null_mut(),
+        }));
+        let mut head = self.records.load(Ordering::Relaxed);
+        loop {
+            // SAFETY: the record is not shared before the exchange succeeds
+            unsafe { (*record).next = head };
+            match self.records.compare_exchange_weak(
+                head,
+                record,
+                Ordering::Release,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => break,
+                Err(new) => head = new,
+            }
+        }
+        // SAFETY: records live as long as the domain
+        Guard {
+            record: unsafe { &*record },
+        }
+    }
+
+    /// Frees `ptr` as soon as no hazard record holds it anymore.
+    ///
+    /// # Safety
+    /// `ptr` has to come from `Box::<T>::into_raw`, it has to be unlinked already,
+    /// so that no thread can load it anew, and it must not be retired twice.
+    pub(crate) unsafe fn retire<T>(&self, ptr: *mut T) {
+        let retired = Box::into_raw(Box::new(Retired {
+            ptr: ptr as *mut u8,
+            delete: delete::<T>,
+            next: ptr::null_mut(),
+        }));
+        self.push_retired(retired, retired);
+
+        if self.retired_count.fetch_add(1, Ordering::Relaxed) % SCAN_EVERY == SCAN_EVERY - 1 {
+            self.scan();
+        }
+    }
+
+    /// Pushes the chain from `first` to `last` onto the retired list.
+    fn push_retired(&self, first: *mut Retired, last: *mut Retired) {
+        let mut head = self.retired.load(Ordering::Relaxed);
+        loop {
+            // SAFETY: the chain is owned by the caller until the exchange succeeds
+            unsafe { (*last).next = head };
+            match self.retired.compare_exchange_weak(
+                head,
+                first,
+                Ordering::Release,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => return,
+                Err(new) => head = new,
+            }
+        }
+    }
+
+    /// Takes the whole retired list, frees every node that is not protected
+    /// and puts the others back.
+    pub(crate) fn scan(&self) {
+        // The unlinking exchanges of the lists are only `AcqRel`, and a release store may
+        // be reordered after a later SeqCst load of another location. Without the fence
+        // the records could be read before the unlink is visible, so a `protect` that
+        // published the node and then re-checked the source would still see it linked,
+        // while this scan misses its hazard and frees the node.
+        atomic::fence(Ordering::SeqCst);
+
+        let mut hazards = Vec::new();
+        let mut curr = self.records.load(Ordering::Acquire);
+        while !curr.is_null() {
+            // SAFETY: records live as long as the domain
+            let record = unsafe { &*curr };
+            let hazard = record.hazard.load(Ordering::SeqCst);
+            if !hazard.is_null() {
+                hazards.push(hazard);
+            }
+            curr = record.next;
+        }
+
+        let mut kept: (*mut Retired, *mut Retired) = (ptr::null_mut(), ptr::null_mut());
+        let mut curr = self.retired.swap(ptr::null_mut(), Ordering::Acquire);
+        while !curr.is_null() {
+            // SAFETY: the swap made this thread the only owner of the chain
+            let retired = unsafe { &mut *curr };
+            let next = retired.next;
+            if hazards.contains(&retired.ptr) {
+                retired.next = kept.0;
+                if kept.1.is_null() {
+                    kept.1 = curr;
+                }
+                kept.0 = curr;
+            } else {
+                // SAFETY: the node is unlinked and no hazard record holds it,
+                // so no thread can reach it anymore
+                unsafe {
+                    (retired.delete)(retired.ptr);
+                    drop(Box::from_raw(curr));
+                }
+            }
+            curr = next;
+        }
+
+        if !kept.0.is_null() {
+            self.push_retired(kept.0, kept.1);
+        }
+    }
+}
+
+impl Default for Domain {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// With `&mut self` no guard is alive, so every retired node can be freed.
+impl Drop for Domain {
+    fn drop(&mut self) {
+        let mut curr = *self.retired.get_mut();
+        while !curr.is_null() {
+            // SAFETY: the domain owns the retired nodes and nobody can protect them anymore
+            unsafe {
+                let retired = Box::from_raw(curr);
+                (retired.delete)(retired.ptr);
+                curr = retired.next;
+            }
+        }
+
+        let mut curr = *self.records.get_mut();
+        while !curr.is_null() {
+            // SAFETY: no guard outlives the domain
+            let record = unsafe { Box::from_raw(curr) };
+            curr = record.next;
+        }
+    }
+}
+
+/// Guard owns one hazard record until it is dropped.
+pub(crate) struct Guard<'a> {
+    record: &'a Record,
+}
+
+impl Guard<'_> {
+    /// Loads `src` and protects the pointer, so that it is not freed before the guard
+    /// protects something else. Retries until `src` still holds the pointer after
+    /// it was published, otherwise it might have been retired in between.
+    pub(crate) fn protect<T>(&self, src: &AtomicPtr<T>) -> *mut T {
+        let mut ptr = src.load(Ordering::Relaxed);
+        loop {
+            self.record.hazard.store(ptr as *mut u8, Ordering::SeqCst);
+            let curr = src.load(Ordering::SeqCst);
+            if curr == ptr {
+                return ptr;
+            }
+            ptr = curr;
+        }
+    }
+
+    pub(crate) fn reset(&self) {
+        self.record.hazard.store(ptr::null_mut(), Ordering::Release);
+    }
+}
+
+impl Drop for Guard<'_> {
+    fn drop(&mut self) {
+        self.reset();
+        self.record.active.store(false, Ordering::Release);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+
+    fn counted(counter: &Arc<()>) -> *mut Arc<()> {
+        Box::into_raw(Box::new(Arc::clone(counter)))
+    }
+
+    #[test]
+    fn frees_unprotected() {
+        let counter = Arc::new(());
+        let domain = Domain::new();
+        for _ in 0..10 {
+            // SAFETY: the pointer is not shared
+            unsafe { domain.retire(counted(&counter)) };
+        }
+        assert_eq!(Arc::strong_count(&counter), 11);
+
+        domain.scan();
+        assert_eq!(Arc::strong_count(&counter), 1);
+    }
+
+    #[test]
+    fn keeps_protected() {
+        let counter = Arc::new(());
+        let domain = Domain::new();
+        let shared = AtomicPtr::new(counted(&counter));
+
+        let guard = domain.guard();
+        let ptr = guard.protect(&shared);
+        shared.store(ptr::null_mut(), Ordering::SeqCst);
+        // SAFETY: the pointer is unlinked
+        unsafe { domain.retire(ptr) };
+        domain.scan();
+        assert_eq!(Arc::strong_count(&counter), 2);
+
+        guard.reset();
+        domain.scan();
+        assert_eq!(Arc::strong_count(&counter), 1);
+    }
+
+    #[test]
+    fn reuses_records() {
+        let domain = Domain::new();
+        let a = domain.guard();
+        let b = domain.guard();
+        assert!(!ptr::eq(a.record, b.record));
+
+        let record = a.record as *const Record;
+        drop(a);
+        let c = domain.guard();
+        assert!(ptr::eq(c.record, record));
+    }
+
+    #[test]
+    fn frees_on_drop() {
+        let counter = Arc::new(());
+        let domain = Domain::new();
+        let guard = domain.guard();
+        let ptr = counted(&counter);
+        let shared = AtomicPtr::new(ptr);
+        guard.protect(&shared);
+        // SAFETY: nothing reads through `shared` anymore
+        unsafe { domain.retire(ptr) };
+        drop(guard);
+
+        drop(domain);
+        assert_eq!(Arc::strong_count(&counter), 1);
+    }
+}
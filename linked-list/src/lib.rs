@@ -1,5 +1,18 @@
-mod double;
-mod queue;
-mod single;
-mod stack;
+#![cfg_attr(all(test, feature = "bench"), feature(test))]
+
+pub mod algorithms;
+pub mod arena;
+pub mod double;
+pub mod double_raw;
+mod hazard;
+pub mod lru;
+pub mod persistent;
+pub mod queue;
+pub mod queue_atomic;
+pub mod queue_raw;
+pub mod single;
+pub mod skip_list;
+pub mod stack;
+pub mod stack_atomic;
+pub mod unrolled;
 // TODO: refcell & rc basics
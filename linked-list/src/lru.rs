@@ -0,0 +1,215 @@
+use std::{collections::HashMap, fmt::Debug, hash::Hash};
+
+use crate::double::{List, NodeRef};
+
+/// LruCache keeps at most `capacity` entries and evicts the least recently used one
+/// when a new entry does not fit.
+/// The entries are kept in a `double::List` from the most to the least recently used,
+/// and the map points at the nodes, so every operation is O(1).
+pub struct LruCache<K, V> {
+    capacity: usize,
+    map: HashMap<K, NodeRef<(K, V)>>,
+    list: List<(K, V)>,
+    on_evict: Option<Box<dyn FnMut(K, V)>>,
+}
+
+impl<K, V> LruCache<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            map: HashMap::with_capacity(capacity),
+            list: List::new(),
+            on_evict: None,
+        }
+    }
+
+    /// Calls `on_evict` with every entry that is evicted by `put` or `resize`.
+    pub fn with_on_evict<F>(capacity: usize, on_evict: F) -> Self
+    where
+        F: FnMut(K, V) + 'static,
+    {
+        Self {
+            on_evict: Some(Box::new(on_evict)),
+            ..Self::new(capacity)
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn contains(&self, key: &K) -> bool {
+        self.map.contains_key(key)
+    }
+
+    /// Returns the value of `key` and marks it as the most recently used.
+    pub fn get(&mut self, key: &K) -> Option<&V> {
+        let node = self.map.get(key)?;
+        self.list.move_to_front(node);
+        Some(&self.list.node_val(node).1)
+    }
+
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        let node = self.map.get(key)?;
+        self.list.move_to_front(node);
+        Some(&mut self.list.node_val_mut(node).1)
+    }
+
+    /// Returns the value of `key` without marking it as used.
+    pub fn peek(&self, key: &K) -> Option<&V> {
+        let node = self.map.get(key)?;
+        Some(&self.list.node_val(node).1)
+    }
+
+    /// Inserts the entry as the most recently used and returns the old value of `key`.
+    /// If the cache is full, the least recently used entry is evicted.
+    pub fn put(&mut self, key: K, val: V) -> Option<V> {
+        if let Some(node) = self.map.get(&key) {
+            self.list.move_to_front(node);
+            return Some(std::mem::replace(&mut self.list.node_val_mut(node).1, val));
+        }
+
+        let node = self.list.push_front_node((key.clone(), val));
+        self.map.insert(key, node);
+        self.evict_to(self.capacity);
+        None
+    }
+
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let node = self.map.remove(key)?;
+        Some(self.list.remove_node(node).1)
+    }
+
+    /// Removes the least recently used entry, without calling the eviction callback.
+    pub fn pop_lru(&mut self) -> Option<(K, V)> {
+        let back = self.list.back_node()?;
+        let node = self
+            .map
+            .remove(&self.list.node_val(&back).0)
+            .expect("every entry is in the map");
+        drop(back);
+        Some(self.list.remove_node(node))
+    }
+
+    /// Changes the capacity and evicts the least recently used entries that do not fit anymore.
+    pub fn resize(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        self.evict_to(capacity);
+    }
+
+    fn evict_to(&mut self, len: usize) {
+        while self.len() > len {
+            let Some((key, val)) = self.pop_lru() else {
+                break;
+            };
+            if let Some(on_evict) = &mut self.on_evict {
+                on_evict(key, val);
+            }
+        }
+    }
+
+    /// Iterates from the most to the least recently used entry.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (&K, &V)> {
+        self.list.iter().map(|(key, val)| (key, val))
+    }
+}
+
+impl<K, V> Debug for LruCache<K, V>
+where
+    K: Hash + Eq + Clone + Debug,
+    V: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        let mut cache = LruCache::new(2);
+        assert!(cache.put(1, "one").is_none());
+        assert!(cache.put(2, "two").is_none());
+        assert_eq!(cache.get(&1), Some(&"one"));
+
+        cache.put(3, "three");
+        assert!(!cache.contains(&2));
+        assert_eq!(cache.get(&2), None);
+        assert_eq!(cache.len(), 2);
+
+        assert_eq!(cache.put(1, "uno"), Some("one"));
+        cache.put(4, "four");
+        assert_eq!(cache.peek(&3), None);
+        assert!(cache.iter().eq([(&4, &"four"), (&1, &"uno")]));
+    }
+
+    #[test]
+    fn peek_does_not_promote() {
+        let mut cache = LruCache::new(2);
+        cache.put("a", 0);
+        cache.put("b", 1);
+        assert_eq!(cache.peek(&"a"), Some(&0));
+
+        cache.put("c", 2);
+        assert!(!cache.contains(&"a"));
+        *cache.get_mut(&"b").unwrap() += 10;
+        assert_eq!(cache.pop_lru(), Some(("c", 2)));
+        assert_eq!(cache.pop_lru(), Some(("b", 11)));
+        assert_eq!(cache.pop_lru(), None);
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn evictions() {
+        let evicted = Rc::new(RefCell::new(Vec::new()));
+        let log = Rc::clone(&evicted);
+        let mut cache = LruCache::with_on_evict(3, move |k, v| log.borrow_mut().push((k, v)));
+
+        for i in 0..5 {
+            cache.put(i, i * 10);
+        }
+        assert_eq!(*evicted.borrow(), [(0, 0), (1, 10)]);
+
+        cache.get(&2);
+        cache.resize(1);
+        assert_eq!(cache.capacity(), 1);
+        assert_eq!(*evicted.borrow(), [(0, 0), (1, 10), (3, 30), (4, 40)]);
+        assert_eq!(format!("{cache:?}"), "{2: 20}");
+
+        assert_eq!(cache.remove(&2), Some(20));
+        cache.pop_lru();
+        cache.resize(0);
+        cache.put(5, 50);
+        assert!(cache.is_empty());
+        assert_eq!(evicted.borrow().len(), 5);
+    }
+
+    #[test]
+    fn drops_values() {
+        let counter = Rc::new(());
+        let mut cache = LruCache::new(4);
+        for i in 0..6 {
+            cache.put(i, Rc::clone(&counter));
+        }
+        assert_eq!(Rc::strong_count(&counter), 5);
+
+        drop(cache);
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+}
@@ -0,0 +1,250 @@
+use std::{fmt::Display, rc::Rc};
+
+type Link<T> = Option<Rc<Node<T>>>;
+
+struct Node<T> {
+    val: T,
+    next: Link<T>,
+}
+
+/// List is an immutable singly linked list. `push` and `tail` return new lists
+/// that share their nodes with the old one, so both are O(1) and the old list stays valid.
+pub struct List<T> {
+    len: usize,
+    head: Link<T>,
+}
+
+impl<T> List<T> {
+    pub fn new() -> Self {
+        Self { len: 0, head: None }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns a new list with `val` in front of this one.
+    pub fn push(&self, val: T) -> Self {
+        Self {
+            len: self.len + 1,
+            head: Some(Rc::new(Node {
+                val,
+                next: self.head.clone(),
+            })),
+        }
+    }
+
+    pub fn head(&self) -> Option<&T> {
+        self.head.as_ref().map(|node| &node.val)
+    }
+
+    /// Returns the list without its head, the tail of an empty list is empty.
+    pub fn tail(&self) -> Self {
+        Self {
+            len: self.len.saturating_sub(1),
+            head: self.head.as_ref().and_then(|node| node.next.clone()),
+        }
+    }
+
+    /// Returns whether both lists are the same nodes, not just equal values.
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        match (&self.head, &other.head) {
+            (None, None) => true,
+            (Some(a), Some(b)) => Rc::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            next: self.head.as_deref(),
+            len: self.len,
+        }
+    }
+}
+
+impl<T> Default for List<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Cloning only copies the pointer to the head.
+impl<T> Clone for List<T> {
+    fn clone(&self) -> Self {
+        Self {
+            len: self.len,
+            head: self.head.clone(),
+        }
+    }
+}
+
+/// Drops the nodes one by one as long as this list is their only owner.
+/// The first shared node is left to the other lists.
+impl<T> Drop for List<T> {
+    fn drop(&mut self) {
+        let mut link = self.head.take();
+        while let Some(node) = link {
+            match Rc::try_unwrap(node) {
+                Ok(mut node) => link = node.next.take(),
+                Err(_) => break,
+            }
+        }
+    }
+}
+
+/// Builds the list back to front, so it iterates in the same order as `iter`.
+impl<T> FromIterator<T> for List<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let vals: Vec<_> = iter.into_iter().collect();
+        vals.into_iter()
+            .rev()
+            .fold(List::new(), |list, val| list.push(val))
+    }
+}
+
+impl<T> PartialEq for List<T>
+where
+    T: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<T> Eq for List<T> where T: Eq {}
+
+pub struct Iter<'a, T> {
+    next: Option<&'a Node<T>>,
+    len: usize,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let node = self.next?;
+        self.next = node.next.as_deref();
+        self.len -= 1;
+        Some(&node.val)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+impl<'a, T> IntoIterator for &'a List<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<T> Display for List<T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("[")?;
+        for (i, val) in self.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            f.write_fmt(format_args!("{}", val))?;
+        }
+        f.write_str("]")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        let empty = List::new();
+        assert!(empty.head().is_none());
+        assert!(empty.tail().is_empty());
+
+        let one = empty.push(1);
+        let two = one.push(2);
+        let three = two.push(3);
+        assert_eq!(three.len(), 3);
+        assert_eq!(three.head(), Some(&3));
+        assert_eq!(three.tail().head(), Some(&2));
+        assert_eq!(three.to_string(), "[3, 2, 1]");
+
+        // the old lists are unchanged
+        assert_eq!(one.to_string(), "[1]");
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn shares_tails() {
+        let base: List<_> = (0..3).collect();
+        assert_eq!(base.to_string(), "[0, 1, 2]");
+
+        let a = base.push(10);
+        let b = base.push(20);
+        assert!(a.tail().ptr_eq(&b.tail()));
+        assert!(a.tail().ptr_eq(&base));
+        assert!(!a.ptr_eq(&b));
+        assert!(a.tail() == b.tail());
+        assert!(a != b);
+        assert!(base.clone().ptr_eq(&base));
+    }
+
+    #[test]
+    fn backtracking() {
+        fn paths(path: &List<u8>, depth: usize, out: &mut Vec<String>) {
+            if depth == 0 {
+                out.push(path.to_string());
+                return;
+            }
+            for step in 0..2 {
+                paths(&path.push(step), depth - 1, out);
+            }
+        }
+
+        let mut out = Vec::new();
+        paths(&List::new(), 2, &mut out);
+        assert_eq!(out, ["[0, 0]", "[1, 0]", "[0, 1]", "[1, 1]"]);
+    }
+
+    #[test]
+    fn drops_shared_nodes_once() {
+        let counter = Rc::new(());
+        let base = List::new().push(Rc::clone(&counter));
+        let a = base.push(Rc::clone(&counter));
+        let b = base.push(Rc::clone(&counter));
+        assert_eq!(Rc::strong_count(&counter), 4);
+
+        drop(base);
+        drop(a);
+        assert_eq!(Rc::strong_count(&counter), 3);
+        assert_eq!(b.iter().len(), 2);
+        drop(b);
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+
+    #[test]
+    fn drops_long_list() {
+        let mut l = List::new();
+        for i in 0..1_000_000 {
+            l = l.push(i);
+        }
+        let shared = l.tail();
+        drop(l);
+        assert_eq!(shared.len(), 999_999);
+    }
+}
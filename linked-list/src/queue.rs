@@ -1,26 +1,20 @@
-use std::{cell::RefCell, rc::Rc};
+use std::{cell::RefCell, marker::PhantomData, rc::Rc};
 
 type Link<T> = Option<Rc<RefCell<Node<T>>>>;
 
-#[allow(dead_code)]
 struct Node<T> {
     val: T,
     next: Link<T>,
 }
 
-#[allow(dead_code)]
-struct Queue<T> {
+pub struct Queue<T> {
     length: usize,
     head: Link<T>,
     tail: Link<T>,
 }
 
-#[allow(dead_code)]
-impl<T> Queue<T>
-where
-    T: Copy,
-{
-    fn new() -> Self {
+impl<T> Queue<T> {
+    pub fn new() -> Self {
         Self {
             length: 0,
             head: None,
@@ -28,24 +22,38 @@ where
         }
     }
 
-    fn peek(&self) -> Option<T> {
-        self.head.as_ref().map(|r| r.borrow().val)
+    pub fn len(&self) -> usize {
+        self.length
     }
 
-    fn deque(&mut self) -> Option<T> {
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// Like `Iter`, this hands out a reference without keeping the `RefCell` borrowed.
+    pub fn peek(&self) -> Option<&T> {
+        // SAFETY: the queue keeps the head alive and is borrowed as long as the reference
+        self.head
+            .as_ref()
+            .map(|node| unsafe { &(*node.as_ptr()).val })
+    }
+
+    pub fn deque(&mut self) -> Option<T> {
         let node = self.head.take()?;
-        self.head = node.borrow().next.clone();
+        self.head = node.borrow_mut().next.take();
 
         self.length -= 1;
         if self.length == 0 {
             self.tail = None;
         }
 
-        let res = node.borrow().val;
-        Some(res)
+        match Rc::try_unwrap(node) {
+            Ok(node) => Some(node.into_inner().val),
+            Err(_) => unreachable!("a dequed node is not linked anymore"),
+        }
     }
 
-    fn enqueue(&mut self, val: T) {
+    pub fn enqueue(&mut self, val: T) {
         let node = Some(Rc::new(RefCell::new(Node { val, next: None })));
         if self.length == 0 {
             self.head = node.clone();
@@ -60,6 +68,111 @@ where
         }
         self.length += 1;
     }
+
+    /// Iterates from the front to the back of the queue.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            next: self.head.clone(),
+            queue: PhantomData,
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            next: self.head.clone(),
+            queue: PhantomData,
+        }
+    }
+}
+
+impl<T> Default for Queue<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for Queue<T> {
+    fn drop(&mut self) {
+        while self.deque().is_some() {}
+    }
+}
+
+/// Iter hands out references into the `RefCell`s of the nodes without borrowing them.
+/// This is fine, because the queue is borrowed for `'a` and all its methods that
+/// borrow a value need the queue.
+pub struct Iter<'a, T> {
+    next: Link<T>,
+    queue: PhantomData<&'a Queue<T>>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let node = self.next.take()?;
+        self.next = node.borrow().next.clone();
+        // SAFETY: the queue keeps the node alive and is borrowed for 'a
+        Some(unsafe { &(*node.as_ptr()).val })
+    }
+}
+
+pub struct IterMut<'a, T> {
+    next: Link<T>,
+    queue: PhantomData<&'a mut Queue<T>>,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<&'a mut T> {
+        let node = self.next.take()?;
+        self.next = node.borrow().next.clone();
+        // SAFETY: the queue keeps the node alive and is borrowed mutably for 'a,
+        // every node is handed out once
+        Some(unsafe { &mut (*node.as_ptr()).val })
+    }
+}
+
+/// Deques the values from the front to the back.
+pub struct IntoIter<T>(Queue<T>);
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.0.deque()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0.length, Some(self.0.length))
+    }
+}
+
+impl<T> IntoIterator for Queue<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter(self)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Queue<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut Queue<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> IterMut<'a, T> {
+        self.iter_mut()
+    }
 }
 
 #[cfg(test)]
@@ -72,7 +185,7 @@ mod tests {
 
         assert!(q.peek().is_none());
         q.enqueue(0);
-        assert!(q.peek().is_some_and(|i| i == 0));
+        assert!(q.peek().is_some_and(|i| *i == 0));
         assert!(q.deque().is_some_and(|i| i == 0));
         assert!(q.peek().is_none());
         assert!(q.deque().is_none());
@@ -80,10 +193,34 @@ mod tests {
         q.enqueue(1);
         q.enqueue(2);
         q.enqueue(3);
-        assert!(q.peek().is_some_and(|i| i == 1));
+        assert!(q.peek().is_some_and(|i| *i == 1));
         assert!(q.deque().is_some_and(|i| i == 1));
         assert!(q.deque().is_some_and(|i| i == 2));
         assert!(q.deque().is_some_and(|i| i == 3));
         assert!(q.deque().is_none());
     }
+
+    #[test]
+    fn iterators() {
+        let mut q = Queue::new();
+        for i in 0..3 {
+            q.enqueue(i.to_string());
+        }
+
+        assert!(q.iter().eq(["0", "1", "2"]));
+        for val in &mut q {
+            val.push('?');
+        }
+        assert!(q.deque().is_some_and(|v| v == "0?"));
+        q.enqueue("3".to_string());
+        assert_eq!(q.into_iter().collect::<Vec<_>>(), ["1?", "2?", "3"]);
+    }
+
+    #[test]
+    fn drops_long_queue() {
+        let mut q = Queue::new();
+        for i in 0..1_000_000 {
+            q.enqueue(i);
+        }
+    }
 }
@@ -0,0 +1,339 @@
+use std::{
+    marker::PhantomData,
+    mem::MaybeUninit,
+    ptr,
+    sync::atomic::{AtomicPtr, Ordering},
+};
+
+use crate::hazard::Domain;
+
+struct Node<T> {
+    /// Uninitialized in the sentinel, every other value is moved out by the `deque`
+    /// that turns its node into the new sentinel.
+    val: MaybeUninit<T>,
+    next: AtomicPtr<Node<T>>,
+}
+
+impl<T> Node<T> {
+    fn new(val: MaybeUninit<T>) -> *mut Self {
+        Box::into_raw(Box::new(Self {
+            val,
+            next: AtomicPtr::new(ptr::null_mut()),
+        }))
+    }
+}
+
+/// Queue is a lock-free Michael–Scott queue with the same `enqueue`/`deque`/`peek`
+/// as `queue::Queue`, that can be shared between any number of producers and consumers.
+/// `head` always points at a sentinel node whose successor holds the front value,
+/// and `tail` points at the last or the second to last node. Threads that find `tail`
+/// lagging behind move it forward before they continue.
+pub struct Queue<T> {
+    head: AtomicPtr<Node<T>>,
+    tail: AtomicPtr<Node<T>>,
+    domain: Domain,
+    marker: PhantomData<Box<Node<T>>>,
+}
+
+// SAFETY: values are moved between threads, but never shared,
+// except by `peek`, which copies them
+unsafe impl<T: Send> Send for Queue<T> {}
+unsafe impl<T: Send> Sync for Queue<T> {}
+
+impl<T> Queue<T> {
+    pub fn new() -> Self {
+        let sentinel = Node::new(MaybeUninit::uninit());
+        Self {
+            head: AtomicPtr::new(sentinel),
+            tail: AtomicPtr::new(sentinel),
+            domain: Domain::new(),
+            marker: PhantomData,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        let guard = self.domain.guard();
+        let head = guard.protect(&self.head);
+        // SAFETY: the guard keeps the sentinel from being freed
+        unsafe { (*head).next.load(Ordering::Acquire).is_null() }
+    }
+
+    /// Copies the front value. Like `stack_atomic::Stack::peek` this needs `T: Copy`,
+    /// because a concurrent `deque` can drop the value while it is read.
+    pub fn peek(&self) -> Option<T>
+    where
+        T: Copy,
+    {
+        let head_guard = self.domain.guard();
+        let next_guard = self.domain.guard();
+        loop {
+            let head = head_guard.protect(&self.head);
+            // SAFETY: the guard keeps the sentinel from being freed
+            let next = next_guard.protect(unsafe { &(*head).next });
+            if head != self.head.load(Ordering::Acquire) {
+                continue;
+            }
+            if next.is_null() {
+                return None;
+            }
+
+            // SAFETY: `head` was still the sentinel after `next` was protected,
+            // so `next` was not dequeued yet and holds a value
+            return Some(unsafe { (*next).val.assume_init() });
+        }
+    }
+
+    pub fn deque(&self) -> Option<T> {
+        let head_guard = self.domain.guard();
+        let next_guard = self.domain.guard();
+        loop {
+            let head = head_guard.protect(&self.head);
+            let tail = self.tail.load(Ordering::Acquire);
+            // SAFETY: the guard keeps the sentinel from being freed
+            let next = next_guard.protect(unsafe { &(*head).next });
+            if head != self.head.load(Ordering::Acquire) {
+                continue;
+            }
+            if next.is_null() {
+                return None;
+            }
+            if head == tail {
+                // an enqueue linked `next` but did not move the tail yet
+                let _ = self
+                    .tail
+                    .compare_exchange(tail, next, Ordering::AcqRel, Ordering::Relaxed);
+                continue;
+            }
+
+            if self
+                .head
+                .compare_exchange(head, next, Ordering::AcqRel, Ordering::Acquire)
+                .is_ok()
+            {
+                // SAFETY: only the thread that made `next` the sentinel takes its value,
+                // and the old sentinel is unlinked and retired once
+                unsafe {
+                    let val = (*next).val.assume_init_read();
+                    head_guard.reset();
+                    next_guard.reset();
+                    self.domain.retire(head);
+                    return Some(val);
+                }
+            }
+        }
+    }
+
+    pub fn enqueue(&self, val: T) {
+        let node = Node::new(MaybeUninit::new(val));
+        let guard = self.domain.guard();
+        loop {
+            let tail = guard.protect(&self.tail);
+            // SAFETY: the guard keeps the tail from being freed
+            let next = unsafe { (*tail).next.load(Ordering::Acquire) };
+            if tail != self.tail.load(Ordering::Acquire) {
+                continue;
+            }
+
+            if !next.is_null() {
+                // help the enqueue that linked `next` to move the tail
+                let _ = self
+                    .tail
+                    .compare_exchange(tail, next, Ordering::AcqRel, Ordering::Relaxed);
+                continue;
+            }
+
+            // SAFETY: the guard keeps the tail from being freed
+            let linked = unsafe {
+                (*tail)
+                    .next
+                    .compare_exchange(next, node, Ordering::AcqRel, Ordering::Relaxed)
+                    .is_ok()
+            };
+            if linked {
+                let _ = self
+                    .tail
+                    .compare_exchange(tail, node, Ordering::AcqRel, Ordering::Relaxed);
+                return;
+            }
+        }
+    }
+}
+
+impl<T> Default for Queue<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Frees the sentinel and drops the values of all nodes after it,
+/// dequeued sentinels are freed by the domain.
+impl<T> Drop for Queue<T> {
+    fn drop(&mut self) {
+        let sentinel = *self.head.get_mut();
+        // SAFETY: with `&mut self` the queue is the only owner of its nodes
+        let mut curr = unsafe { Box::from_raw(sentinel) }.next.into_inner();
+        while !curr.is_null() {
+            // SAFETY: every node after the sentinel holds a value
+            let mut node = unsafe { Box::from_raw(curr) };
+            unsafe { node.val.assume_init_drop() };
+            curr = node.next.into_inner();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashSet,
+        sync::{Arc, Barrier},
+        thread,
+    };
+
+    use super::*;
+
+    const THREADS: usize = 4;
+    const PER_THREAD: usize = 10_000;
+
+    #[test]
+    fn it_works() {
+        let q = Queue::new();
+
+        assert!(q.peek().is_none());
+        q.enqueue(0);
+        assert_eq!(q.peek(), Some(0));
+        assert_eq!(q.deque(), Some(0));
+        assert!(q.peek().is_none());
+        assert!(q.deque().is_none());
+
+        q.enqueue(1);
+        q.enqueue(2);
+        q.enqueue(3);
+        assert_eq!(q.peek(), Some(1));
+        assert_eq!(q.deque(), Some(1));
+        assert_eq!(q.deque(), Some(2));
+        assert_eq!(q.deque(), Some(3));
+        assert!(q.deque().is_none());
+        assert!(q.is_empty());
+    }
+
+    #[test]
+    fn drops_values() {
+        let counter = Arc::new(());
+        let q = Queue::new();
+        for _ in 0..200 {
+            q.enqueue(Arc::clone(&counter));
+        }
+        for _ in 0..150 {
+            q.deque();
+        }
+        assert_eq!(Arc::strong_count(&counter), 51);
+
+        drop(q);
+        assert_eq!(Arc::strong_count(&counter), 1);
+    }
+
+    #[test]
+    fn single_producer_single_consumer() {
+        let q = Arc::new(Queue::new());
+        let producer = {
+            let q = Arc::clone(&q);
+            thread::spawn(move || {
+                for i in 0..PER_THREAD {
+                    q.enqueue(i);
+                }
+            })
+        };
+
+        let mut expected = 0;
+        while expected < PER_THREAD {
+            if let Some(peeked) = q.peek() {
+                assert_eq!(peeked, expected);
+                assert_eq!(q.deque(), Some(expected));
+                expected += 1;
+            }
+        }
+        producer.join().unwrap();
+        assert!(q.is_empty());
+    }
+
+    /// Every consumer has to see the values of one producer in the order they were enqueued,
+    /// and every value has to be dequeued exactly once.
+    #[test]
+    fn multi_producer_multi_consumer() {
+        let q = Arc::new(Queue::new());
+        let barrier = Arc::new(Barrier::new(THREADS * 2));
+
+        let producers: Vec<_> = (0..THREADS)
+            .map(|t| {
+                let q = Arc::clone(&q);
+                let barrier = Arc::clone(&barrier);
+                thread::spawn(move || {
+                    barrier.wait();
+                    for i in 0..PER_THREAD {
+                        q.enqueue((t, i));
+                    }
+                })
+            })
+            .collect();
+        let consumers: Vec<_> = (0..THREADS)
+            .map(|_| {
+                let q = Arc::clone(&q);
+                let barrier = Arc::clone(&barrier);
+                thread::spawn(move || {
+                    barrier.wait();
+                    let mut last = [None; THREADS];
+                    let mut dequeued = Vec::new();
+                    for _ in 0..PER_THREAD {
+                        if let Some((t, i)) = q.deque() {
+                            assert!(last[t] < Some(i), "{t}: {i} after {:?}", last[t]);
+                            last[t] = Some(i);
+                            dequeued.push((t, i));
+                        }
+                    }
+                    dequeued
+                })
+            })
+            .collect();
+
+        for producer in producers {
+            producer.join().unwrap();
+        }
+        let mut seen = HashSet::new();
+        for consumer in consumers {
+            for val in consumer.join().unwrap() {
+                assert!(seen.insert(val), "{val:?} was dequeued twice");
+            }
+        }
+        while let Some(val) = q.deque() {
+            assert!(seen.insert(val), "{val:?} was dequeued twice");
+        }
+        assert_eq!(seen.len(), THREADS * PER_THREAD);
+    }
+
+    #[test]
+    fn concurrent_drops() {
+        let counter = Arc::new(());
+        let q = Arc::new(Queue::new());
+        let handles: Vec<_> = (0..THREADS)
+            .map(|_| {
+                let q = Arc::clone(&q);
+                let counter = Arc::clone(&counter);
+                thread::spawn(move || {
+                    for i in 0..PER_THREAD {
+                        q.enqueue(Arc::clone(&counter));
+                        if i % 2 == 0 {
+                            q.deque();
+                        }
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(Arc::strong_count(&counter), 1 + THREADS * PER_THREAD / 2);
+        drop(q);
+        assert_eq!(Arc::strong_count(&counter), 1);
+    }
+}
@@ -0,0 +1,274 @@
+use std::{marker::PhantomData, ptr::NonNull};
+
+type Link<T> = Option<NonNull<Node<T>>>;
+
+struct Node<T> {
+    val: T,
+    next: Link<T>,
+}
+
+/// Queue has the same API as `queue::Queue`, but the nodes are linked with raw pointers
+/// instead of `Rc<RefCell<_>>`. The queue owns all nodes from `head` to `tail`,
+/// they are allocated with `Box` by `enqueue` and freed by `deque`.
+pub struct Queue<T> {
+    length: usize,
+    head: Link<T>,
+    tail: Link<T>,
+    marker: PhantomData<Box<Node<T>>>,
+}
+
+impl<T> Queue<T> {
+    pub fn new() -> Self {
+        Self {
+            length: 0,
+            head: None,
+            tail: None,
+            marker: PhantomData,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        // SAFETY: the head belongs to the queue, which is borrowed as long as the reference
+        self.head.map(|node| unsafe { &(*node.as_ptr()).val })
+    }
+
+    pub fn deque(&mut self) -> Option<T> {
+        // SAFETY: the head belongs to the queue and is not linked anymore afterwards
+        let node = unsafe { Box::from_raw(self.head?.as_ptr()) };
+        self.head = node.next;
+
+        self.length -= 1;
+        if self.length == 0 {
+            self.tail = None;
+        }
+
+        Some(node.val)
+    }
+
+    pub fn enqueue(&mut self, val: T) {
+        let node = NonNull::from(Box::leak(Box::new(Node { val, next: None })));
+        match self.tail {
+            None => self.head = Some(node),
+            // SAFETY: the tail belongs to the queue
+            Some(tail) => unsafe { (*tail.as_ptr()).next = Some(node) },
+        }
+        self.tail = Some(node);
+        self.length += 1;
+    }
+
+    /// Iterates from the front to the back of the queue.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            next: self.head,
+            queue: PhantomData,
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            next: self.head,
+            queue: PhantomData,
+        }
+    }
+}
+
+impl<T> Default for Queue<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for Queue<T> {
+    fn drop(&mut self) {
+        while self.deque().is_some() {}
+    }
+}
+
+pub struct Iter<'a, T> {
+    next: Link<T>,
+    queue: PhantomData<&'a Queue<T>>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        self.next.map(|node| {
+            // SAFETY: the queue is borrowed for 'a
+            let node = unsafe { &*node.as_ptr() };
+            self.next = node.next;
+            &node.val
+        })
+    }
+}
+
+pub struct IterMut<'a, T> {
+    next: Link<T>,
+    queue: PhantomData<&'a mut Queue<T>>,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<&'a mut T> {
+        self.next.map(|node| {
+            // SAFETY: the queue is borrowed mutably for 'a, every node is handed out once
+            unsafe {
+                self.next = (*node.as_ptr()).next;
+                &mut (*node.as_ptr()).val
+            }
+        })
+    }
+}
+
+/// Deques the values from the front to the back.
+pub struct IntoIter<T>(Queue<T>);
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.0.deque()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0.length, Some(self.0.length))
+    }
+}
+
+impl<T> IntoIterator for Queue<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter(self)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Queue<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut Queue<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        let mut q = Queue::new();
+
+        assert!(q.peek().is_none());
+        q.enqueue(0);
+        assert!(q.peek().is_some_and(|i| *i == 0));
+        assert!(q.deque().is_some_and(|i| i == 0));
+        assert!(q.peek().is_none());
+        assert!(q.deque().is_none());
+
+        q.enqueue(1);
+        q.enqueue(2);
+        q.enqueue(3);
+        assert_eq!(q.len(), 3);
+        assert!(q.peek().is_some_and(|i| *i == 1));
+        assert!(q.deque().is_some_and(|i| i == 1));
+        assert!(q.deque().is_some_and(|i| i == 2));
+        assert!(q.deque().is_some_and(|i| i == 3));
+        assert!(q.deque().is_none());
+        assert!(q.is_empty());
+    }
+
+    #[test]
+    fn iterators() {
+        let mut q = Queue::new();
+        for i in 0..3 {
+            q.enqueue(i.to_string());
+        }
+
+        assert!(q.iter().eq(["0", "1", "2"]));
+        for val in &mut q {
+            val.push('?');
+        }
+        assert!(q.deque().is_some_and(|v| v == "0?"));
+        q.enqueue("3".to_string());
+        assert_eq!(q.into_iter().collect::<Vec<_>>(), ["1?", "2?", "3"]);
+    }
+
+    #[test]
+    fn drops_values() {
+        let counter = Rc::new(());
+        let mut q = Queue::new();
+        for _ in 0..4 {
+            q.enqueue(Rc::clone(&counter));
+        }
+        q.deque();
+        assert_eq!(Rc::strong_count(&counter), 4);
+
+        drop(q);
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+
+    #[cfg(feature = "bench")]
+    mod bench {
+        use super::*;
+
+        extern crate test;
+        use test::{black_box, Bencher};
+
+        use crate::queue;
+
+        const BENCH_LEN: i64 = 1000;
+
+        #[bench]
+        fn bench_raw_enqueue_deque(b: &mut Bencher) {
+            b.iter(|| {
+                let mut q = Queue::new();
+                let mut sum = 0;
+                for i in 0..BENCH_LEN {
+                    q.enqueue(black_box(i));
+                    if i % 3 == 0 {
+                        sum += q.deque().unwrap_or_default();
+                    }
+                }
+                sum + q.iter().sum::<i64>()
+            })
+        }
+
+        #[bench]
+        fn bench_rc_enqueue_deque(b: &mut Bencher) {
+            b.iter(|| {
+                let mut q = queue::Queue::new();
+                let mut sum = 0;
+                for i in 0..BENCH_LEN {
+                    q.enqueue(black_box(i));
+                    if i % 3 == 0 {
+                        sum += q.deque().unwrap_or_default();
+                    }
+                }
+                sum + q.iter().sum::<i64>()
+            })
+        }
+    }
+}
@@ -2,29 +2,23 @@ use std::fmt::Display;
 
 use anyhow::{anyhow, Error};
 
-type Link<T> = Option<Box<Node<T>>>;
+pub(crate) type Link<T> = Option<Box<Node<T>>>;
 
-#[allow(dead_code)]
-struct Node<T> {
-    val: T,
-    next: Link<T>,
+pub(crate) struct Node<T> {
+    pub(crate) val: T,
+    pub(crate) next: Link<T>,
 }
 
-#[allow(dead_code)]
-struct List<T> {
-    head: Link<T>,
+pub struct List<T> {
+    pub(crate) head: Link<T>,
 }
 
-#[allow(dead_code)]
-impl<T> List<T>
-where
-    T: Copy,
-{
-    fn new() -> Self {
+impl<T> List<T> {
+    pub fn new() -> Self {
         Self { head: None }
     }
 
-    fn push(&mut self, val: T) {
+    pub fn push(&mut self, val: T) {
         let node = Box::new(Node {
             val,
             next: self.head.take(),
@@ -32,58 +26,58 @@ where
         self.head = Some(node);
     }
 
-    fn pop(&mut self) -> Result<T, Error> {
-        let node = self.head.take().ok_or(anyhow!("empty list"))?;
+    pub fn pop(&mut self) -> Result<T, Error> {
+        let node = self.head.take().ok_or_else(|| anyhow!("empty list"))?;
         self.head = node.next;
         Ok(node.val)
     }
 
-    fn get_at(&mut self, i: usize) -> Result<T, Error> {
-        let mut node = self.head.as_ref().ok_or(anyhow!("empty list"))?;
+    pub fn get_at(&self, i: usize) -> Result<&T, Error> {
+        let mut node = self.head.as_ref().ok_or_else(|| anyhow!("empty list"))?;
         for _ in 0..i {
             node = node
                 .next
                 .as_ref()
-                .ok_or(anyhow!("index {i} out of bounds"))?;
+                .ok_or_else(|| anyhow!("index {i} out of bounds"))?;
         }
 
-        Ok(node.val)
+        Ok(&node.val)
     }
 
-    fn delete_at(&mut self, i: usize) -> Result<T, Error> {
+    pub fn delete_at(&mut self, i: usize) -> Result<T, Error> {
         if i == 0 {
             return self.pop();
         }
 
-        let mut last = self.head.as_mut().ok_or(anyhow!("empty list"))?;
+        let mut last = self.head.as_mut().ok_or_else(|| anyhow!("empty list"))?;
         for _ in 1..i {
             last = last
                 .next
                 .as_mut()
-                .ok_or(anyhow!("index {i} is out of bounds"))?;
+                .ok_or_else(|| anyhow!("index {i} is out of bounds"))?;
         }
 
         let curr = last
             .next
             .take()
-            .ok_or(anyhow!("index {i} is out of bounds"))?;
+            .ok_or_else(|| anyhow!("index {i} is out of bounds"))?;
         last.next = curr.next;
 
         Ok(curr.val)
     }
 
-    fn insert_at(&mut self, i: usize, val: T) -> Result<(), Error> {
+    pub fn insert_at(&mut self, i: usize, val: T) -> Result<(), Error> {
         if i == 0 {
             self.push(val);
             return Ok(());
         }
 
-        let mut last = self.head.as_mut().ok_or(anyhow!("empty list"))?;
+        let mut last = self.head.as_mut().ok_or_else(|| anyhow!("empty list"))?;
         for _ in 1..i {
             last = last
                 .next
                 .as_mut()
-                .ok_or(anyhow!("index {i} is out of bounds"))?;
+                .ok_or_else(|| anyhow!("index {i} is out of bounds"))?;
         }
 
         let next = last.next.take();
@@ -92,6 +86,99 @@ where
 
         Ok(())
     }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            next: self.head.as_deref(),
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            next: self.head.as_deref_mut(),
+        }
+    }
+}
+
+impl<T> Default for List<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Drops the nodes one by one, the default drop would recurse once per node.
+impl<T> Drop for List<T> {
+    fn drop(&mut self) {
+        let mut link = self.head.take();
+        while let Some(mut node) = link {
+            link = node.next.take();
+        }
+    }
+}
+
+pub struct Iter<'a, T> {
+    next: Option<&'a Node<T>>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let node = self.next?;
+        self.next = node.next.as_deref();
+        Some(&node.val)
+    }
+}
+
+pub struct IterMut<'a, T> {
+    next: Option<&'a mut Node<T>>,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<&'a mut T> {
+        let node = self.next.take()?;
+        self.next = node.next.as_deref_mut();
+        Some(&mut node.val)
+    }
+}
+
+pub struct IntoIter<T>(List<T>);
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.0.pop().ok()
+    }
+}
+
+impl<T> IntoIterator for List<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter(self)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a List<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut List<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> IterMut<'a, T> {
+        self.iter_mut()
+    }
 }
 
 impl<T> Display for List<T>
@@ -100,15 +187,11 @@ where
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_str("[")?;
-        let mut link = self.head.as_ref();
-        let mut i = 0;
-        while let Some(node) = link {
+        for (i, val) in self.iter().enumerate() {
             if i > 0 {
                 f.write_str(", ")?;
             }
-            i += 1;
-            f.write_fmt(format_args!("{}", node.val))?;
-            link = node.next.as_ref();
+            f.write_fmt(format_args!("{}", val))?;
         }
         f.write_str("]")?;
         Ok(())
@@ -126,10 +209,10 @@ mod tests {
         l.push(1);
         l.push(0);
         assert!(l.insert_at(1, -1).is_ok());
-        assert!(l.get_at(1).is_ok_and(|i| i == -1));
-        assert!(l.get_at(0).is_ok_and(|i| i == 0));
-        assert!(l.get_at(2).is_ok_and(|i| i == 1));
-        assert!(l.get_at(3).is_ok_and(|i| i == 2));
+        assert!(l.get_at(1).is_ok_and(|i| *i == -1));
+        assert!(l.get_at(0).is_ok_and(|i| *i == 0));
+        assert!(l.get_at(2).is_ok_and(|i| *i == 1));
+        assert!(l.get_at(3).is_ok_and(|i| *i == 2));
     }
 
     #[test]
@@ -140,8 +223,8 @@ mod tests {
         l.push(0);
         assert!(l.delete_at(1).is_ok_and(|i| i == 1));
         assert!(l.delete_at(3).is_err());
-        assert!(l.get_at(0).is_ok_and(|i| i == 0));
-        assert!(l.get_at(1).is_ok_and(|i| i == 2));
+        assert!(l.get_at(0).is_ok_and(|i| *i == 0));
+        assert!(l.get_at(1).is_ok_and(|i| *i == 2));
     }
 
     #[test]
@@ -150,7 +233,7 @@ mod tests {
         l.push(0);
         l.push(1);
         l.push(2);
-        assert!(l.get_at(1).is_ok_and(|i| i == 1));
+        assert!(l.get_at(1).is_ok_and(|i| *i == 1));
         assert!(l.get_at(3).is_err());
     }
 
@@ -165,4 +248,28 @@ mod tests {
         assert!(l.pop().is_ok_and(|i| i == 0));
         assert!(l.pop().is_err());
     }
+
+    #[test]
+    fn iterators() {
+        let mut l = List::new();
+        for i in 0..4 {
+            l.push(i.to_string());
+        }
+
+        assert!(l.iter().eq(["3", "2", "1", "0"]));
+        for val in &mut l {
+            val.push('!');
+        }
+        assert_eq!(l.to_string(), "[3!, 2!, 1!, 0!]");
+        assert!(l.get_at(2).is_ok_and(|s| s == "1!"));
+        assert_eq!(l.into_iter().collect::<Vec<_>>(), ["3!", "2!", "1!", "0!"]);
+    }
+
+    #[test]
+    fn drops_long_list() {
+        let mut l = List::new();
+        for i in 0..1_000_000 {
+            l.push(i);
+        }
+    }
 }
@@ -0,0 +1,571 @@
+use std::{
+    fmt::Debug,
+    marker::PhantomData,
+    ops::{Bound, RangeBounds},
+    ptr::NonNull,
+};
+
+use rand::Rng;
+
+/// Enough levels for 2^32 entries.
+const MAX_LEVEL: usize = 32;
+
+type Link<K, V> = Option<NonNull<Node<K, V>>>;
+
+/// Forward is a link on one level together with the number of nodes it skips,
+/// counting the node it points at. A link to the end spans up to `len + 1`.
+struct Forward<K, V> {
+    next: Link<K, V>,
+    span: usize,
+}
+
+impl<K, V> Clone for Forward<K, V> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<K, V> Copy for Forward<K, V> {}
+
+struct Node<K, V> {
+    key: K,
+    val: V,
+    /// One link per level of the node.
+    forward: Vec<Forward<K, V>>,
+}
+
+/// SkipList is an ordered map of linked nodes, where every node is also linked on
+/// a random number of higher levels. Each level skips about half of the nodes of the
+/// level below, so searches take O(log n) steps on average without any rebalancing.
+/// The links count the nodes they skip, which makes `rank` and `at_rank` O(log n) too.
+pub struct SkipList<K, V> {
+    len: usize,
+    /// Number of levels in use.
+    level: usize,
+    head: [Forward<K, V>; MAX_LEVEL],
+    marker: PhantomData<Box<Node<K, V>>>,
+}
+
+/// Nodes before a position on every level, where `None` is the head,
+/// and the rank of each of them.
+type Path<K, V> = ([Link<K, V>; MAX_LEVEL], [usize; MAX_LEVEL]);
+
+/// # Safety
+/// `node` has to be owned by a list that is borrowed for `'a`.
+unsafe fn node_ref<'a, K, V>(node: NonNull<Node<K, V>>) -> &'a Node<K, V> {
+    &*node.as_ptr()
+}
+
+/// Every further level is taken with probability 1/2, one random bit per level.
+fn random_level() -> usize {
+    let bits: u32 = rand::thread_rng().gen();
+    (bits.trailing_ones() as usize + 1).min(MAX_LEVEL)
+}
+
+impl<K, V> SkipList<K, V> {
+    pub fn new() -> Self {
+        Self {
+            len: 0,
+            level: 0,
+            head: [Forward {
+                next: None,
+                span: 1,
+            }; MAX_LEVEL],
+            marker: PhantomData,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn forward(&self, pred: Link<K, V>, level: usize) -> Forward<K, V> {
+        match pred {
+            None => self.head[level],
+            // SAFETY: the list owns all linked nodes
+            Some(node) => unsafe { node_ref(node) }.forward[level],
+        }
+    }
+
+    fn forward_mut(&mut self, pred: Link<K, V>, level: usize) -> &mut Forward<K, V> {
+        match pred {
+            None => &mut self.head[level],
+            Some(node) => {
+                // SAFETY: the list owns all linked nodes and is borrowed mutably
+                let forward = unsafe { &mut (*node.as_ptr()).forward };
+                &mut forward[level]
+            }
+        }
+    }
+
+    /// Walks down from the top level and passes every node for which `before` holds.
+    fn search<F>(&self, before: F) -> Path<K, V>
+    where
+        F: Fn(&K) -> bool,
+    {
+        let mut preds = [None; MAX_LEVEL];
+        let mut ranks = [0; MAX_LEVEL];
+        let mut pred = None;
+        let mut rank = 0;
+
+        for level in (0..self.level).rev() {
+            loop {
+                let forward = self.forward(pred, level);
+                match forward.next {
+                    // SAFETY: the list owns all linked nodes
+                    Some(next) if before(&unsafe { node_ref(next) }.key) => {
+                        rank += forward.span;
+                        pred = Some(next);
+                    }
+                    _ => break,
+                }
+            }
+            preds[level] = pred;
+            ranks[level] = rank;
+        }
+
+        (preds, ranks)
+    }
+
+    /// Returns the first node for which `before` does not hold.
+    fn first_after<F>(&self, before: F) -> Link<K, V>
+    where
+        F: Fn(&K) -> bool,
+    {
+        let (preds, _) = self.search(before);
+        self.forward(preds[0], 0).next
+    }
+
+    fn find(&self, key: &K) -> Link<K, V>
+    where
+        K: Ord,
+    {
+        // SAFETY: the list owns all linked nodes
+        self.first_after(|k| k < key)
+            .filter(|node| unsafe { node_ref(*node) }.key == *key)
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V>
+    where
+        K: Ord,
+    {
+        // SAFETY: the list is borrowed for as long as the reference
+        self.find(key).map(|node| &unsafe { node_ref(node) }.val)
+    }
+
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V>
+    where
+        K: Ord,
+    {
+        // SAFETY: the list is borrowed mutably for as long as the reference
+        self.find(key)
+            .map(|node| unsafe { &mut (*node.as_ptr()).val })
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool
+    where
+        K: Ord,
+    {
+        self.find(key).is_some()
+    }
+
+    /// Inserts the entry and returns the old value of `key`.
+    pub fn insert(&mut self, key: K, val: V) -> Option<V>
+    where
+        K: Ord,
+    {
+        let (mut preds, mut ranks) = self.search(|k| *k < key);
+        if let Some(node) = self.forward(preds[0], 0).next {
+            // SAFETY: the list owns all linked nodes and is borrowed mutably
+            let node = unsafe { &mut *node.as_ptr() };
+            if node.key == key {
+                return Some(std::mem::replace(&mut node.val, val));
+            }
+        }
+
+        let level = random_level();
+        for l in self.level..level {
+            preds[l] = None;
+            ranks[l] = 0;
+            self.head[l] = Forward {
+                next: None,
+                span: self.len + 1,
+            };
+        }
+        self.level = self.level.max(level);
+
+        let rank = ranks[0];
+        let node = NonNull::from(Box::leak(Box::new(Node {
+            key,
+            val,
+            forward: Vec::with_capacity(level),
+        })));
+        for l in 0..level {
+            let pred = self.forward_mut(preds[l], l);
+            let forward = Forward {
+                next: pred.next,
+                span: pred.span - (rank - ranks[l]),
+            };
+            *pred = Forward {
+                next: Some(node),
+                span: rank - ranks[l] + 1,
+            };
+            // SAFETY: the node is not shared yet
+            unsafe { (*node.as_ptr()).forward.push(forward) };
+        }
+        for (l, pred) in preds.into_iter().enumerate().take(self.level).skip(level) {
+            self.forward_mut(pred, l).span += 1;
+        }
+
+        self.len += 1;
+        None
+    }
+
+    pub fn remove(&mut self, key: &K) -> Option<V>
+    where
+        K: Ord,
+    {
+        let (preds, _) = self.search(|k| k < key);
+        let node = self.forward(preds[0], 0).next?;
+        // SAFETY: the list owns all linked nodes
+        if unsafe { node_ref(node) }.key != *key {
+            return None;
+        }
+
+        // SAFETY: the node is unlinked on every level right below, before anything reads it
+        let node = unsafe { Box::from_raw(node.as_ptr()) };
+        for (l, pred) in preds.into_iter().enumerate().take(self.level) {
+            let pred = self.forward_mut(pred, l);
+            match node.forward.get(l) {
+                Some(forward) => {
+                    pred.next = forward.next;
+                    pred.span += forward.span - 1;
+                }
+                None => pred.span -= 1,
+            }
+        }
+        while self.level > 0 && self.head[self.level - 1].next.is_none() {
+            self.level -= 1;
+        }
+
+        self.len -= 1;
+        Some(node.val)
+    }
+
+    pub fn first(&self) -> Option<(&K, &V)> {
+        // SAFETY: the list is borrowed for as long as the references
+        self.head[0].next.map(|node| {
+            let node = unsafe { node_ref(node) };
+            (&node.key, &node.val)
+        })
+    }
+
+    pub fn last(&self) -> Option<(&K, &V)> {
+        let (preds, _) = self.search(|_| true);
+        // SAFETY: the list is borrowed for as long as the references
+        preds[0].map(|node| {
+            let node = unsafe { node_ref(node) };
+            (&node.key, &node.val)
+        })
+    }
+
+    /// Returns the number of keys smaller than `key`, if `key` is in the list.
+    pub fn rank(&self, key: &K) -> Option<usize>
+    where
+        K: Ord,
+    {
+        let (preds, ranks) = self.search(|k| k < key);
+        // SAFETY: the list owns all linked nodes
+        self.forward(preds[0], 0)
+            .next
+            .filter(|node| unsafe { node_ref(*node) }.key == *key)
+            .map(|_| ranks[0])
+    }
+
+    /// Returns the entry with `rank` smaller keys.
+    pub fn at_rank(&self, rank: usize) -> Option<(&K, &V)> {
+        if rank >= self.len {
+            return None;
+        }
+
+        let mut pred = None;
+        let mut passed = 0;
+        for level in (0..self.level).rev() {
+            loop {
+                let forward = self.forward(pred, level);
+                if forward.next.is_none() || passed + forward.span > rank + 1 {
+                    break;
+                }
+                passed += forward.span;
+                pred = forward.next;
+            }
+        }
+
+        // SAFETY: the list is borrowed for as long as the references
+        pred.map(|node| {
+            let node = unsafe { node_ref(node) };
+            (&node.key, &node.val)
+        })
+    }
+
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter {
+            next: self.head[0].next,
+            len: self.len,
+            list: PhantomData,
+        }
+    }
+
+    /// Iterates over the entries with keys in `range` in ascending order.
+    pub fn range<R>(&self, range: R) -> Range<'_, K, V, R>
+    where
+        K: Ord,
+        R: RangeBounds<K>,
+    {
+        let next = match range.start_bound() {
+            Bound::Included(start) => self.first_after(|k| k < start),
+            Bound::Excluded(start) => self.first_after(|k| k <= start),
+            Bound::Unbounded => self.head[0].next,
+        };
+
+        Range {
+            next,
+            range,
+            list: PhantomData,
+        }
+    }
+}
+
+impl<K, V> Default for SkipList<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Frees the nodes along the bottom level.
+impl<K, V> Drop for SkipList<K, V> {
+    fn drop(&mut self) {
+        let mut link = self.head[0].next;
+        while let Some(node) = link {
+            // SAFETY: every node is linked once on the bottom level
+            let node = unsafe { Box::from_raw(node.as_ptr()) };
+            link = node.forward[0].next;
+        }
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for SkipList<K, V>
+where
+    K: Ord,
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut list = Self::new();
+        for (key, val) in iter {
+            list.insert(key, val);
+        }
+        list
+    }
+}
+
+impl<K, V> Debug for SkipList<K, V>
+where
+    K: Debug,
+    V: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+pub struct Iter<'a, K, V> {
+    next: Link<K, V>,
+    len: usize,
+    list: PhantomData<&'a SkipList<K, V>>,
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // SAFETY: the list is borrowed for 'a
+        let node = unsafe { node_ref(self.next?) };
+        self.next = node.forward[0].next;
+        self.len -= 1;
+        Some((&node.key, &node.val))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<K, V> ExactSizeIterator for Iter<'_, K, V> {}
+
+/// Range walks the bottom level from the first key in the range until a key is past its end.
+pub struct Range<'a, K, V, R> {
+    next: Link<K, V>,
+    range: R,
+    list: PhantomData<&'a SkipList<K, V>>,
+}
+
+impl<'a, K, V, R> Iterator for Range<'a, K, V, R>
+where
+    K: Ord,
+    R: RangeBounds<K>,
+{
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // SAFETY: the list is borrowed for 'a
+        let node = unsafe { node_ref(self.next?) };
+        if !self.range.contains(&node.key) {
+            self.next = None;
+            return None;
+        }
+        self.next = node.forward[0].next;
+        Some((&node.key, &node.val))
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a SkipList<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Iter<'a, K, V> {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use rand::seq::SliceRandom;
+
+    use super::*;
+
+    /// Checks the spans of every level against the bottom level.
+    fn spans_are_consistent<K, V>(list: &SkipList<K, V>) -> bool {
+        (0..list.level).all(|level| {
+            let mut pred = None;
+            let mut rank = 0;
+            loop {
+                let forward = list.forward(pred, level);
+                let Some(next) = forward.next else {
+                    return rank + forward.span == list.len + 1;
+                };
+                rank += forward.span;
+                if list.at_rank(rank - 1).map(|(k, _)| k as *const K)
+                    != Some(&unsafe { node_ref(next) }.key as *const K)
+                {
+                    return false;
+                }
+                pred = Some(next);
+            }
+        })
+    }
+
+    #[test]
+    fn it_works() {
+        let mut list = SkipList::new();
+        assert!(list.first().is_none());
+        assert!(list.last().is_none());
+
+        assert!(list.insert(5, "five").is_none());
+        assert!(list.insert(1, "one").is_none());
+        assert!(list.insert(3, "three").is_none());
+        assert_eq!(list.insert(3, "drei"), Some("three"));
+        assert_eq!(list.len(), 3);
+
+        assert_eq!(list.get(&3), Some(&"drei"));
+        assert_eq!(list.get(&4), None);
+        *list.get_mut(&5).unwrap() = "fünf";
+        assert_eq!(list.first(), Some((&1, &"one")));
+        assert_eq!(list.last(), Some((&5, &"fünf")));
+        assert_eq!(format!("{list:?}"), r#"{1: "one", 3: "drei", 5: "fünf"}"#);
+
+        assert_eq!(list.remove(&3), Some("drei"));
+        assert_eq!(list.remove(&3), None);
+        assert!(!list.contains_key(&3));
+        assert!(list.iter().eq([(&1, &"one"), (&5, &"fünf")]));
+    }
+
+    #[test]
+    fn ranks() {
+        let list: SkipList<_, _> = (0..100).map(|i| (i * 2, i)).collect();
+
+        assert_eq!(list.rank(&0), Some(0));
+        assert_eq!(list.rank(&42), Some(21));
+        assert_eq!(list.rank(&43), None);
+        assert_eq!(list.at_rank(21), Some((&42, &21)));
+        assert_eq!(list.at_rank(99), Some((&198, &99)));
+        assert_eq!(list.at_rank(100), None);
+        assert!(spans_are_consistent(&list));
+    }
+
+    #[test]
+    fn ranges() {
+        let list: SkipList<_, _> = (0..20).map(|i| (i, ())).collect();
+        let keys = |r: Vec<(&i32, &())>| r.into_iter().map(|(k, _)| *k).collect::<Vec<_>>();
+
+        assert_eq!(keys(list.range(3..6).collect()), [3, 4, 5]);
+        assert_eq!(keys(list.range(17..).collect()), [17, 18, 19]);
+        assert_eq!(keys(list.range(..=2).collect()), [0, 1, 2]);
+        assert_eq!(
+            keys(
+                list.range((Bound::Excluded(4), Bound::Included(6)))
+                    .collect()
+            ),
+            [5, 6]
+        );
+        assert_eq!(list.range(30..).count(), 0);
+        assert_eq!(list.range(..).count(), 20);
+    }
+
+    #[test]
+    fn matches_btree_map() {
+        let mut rng = rand::thread_rng();
+        let mut list = SkipList::new();
+        let mut map = BTreeMap::new();
+
+        for _ in 0..2000 {
+            let key = rng.gen_range(0..500);
+            if rng.gen_bool(0.3) {
+                assert_eq!(list.remove(&key), map.remove(&key));
+            } else {
+                let val = rng.gen::<u32>();
+                assert_eq!(list.insert(key, val), map.insert(key, val));
+            }
+        }
+
+        assert_eq!(list.len(), map.len());
+        assert!(list.iter().eq(map.iter()));
+        assert_eq!(list.first(), map.first_key_value());
+        assert_eq!(list.last(), map.last_key_value());
+        assert!(list.range(100..200).eq(map.range(100..200)));
+        for (rank, (key, _)) in map.iter().enumerate() {
+            assert_eq!(list.rank(key), Some(rank));
+        }
+        assert!(spans_are_consistent(&list));
+    }
+
+    #[test]
+    fn drops_values() {
+        let counter = std::rc::Rc::new(());
+        let mut keys: Vec<_> = (0..100).collect();
+        keys.shuffle(&mut rand::thread_rng());
+        let mut list = SkipList::new();
+        for key in keys {
+            list.insert(key, std::rc::Rc::clone(&counter));
+        }
+        list.remove(&50);
+        assert_eq!(std::rc::Rc::strong_count(&counter), 100);
+
+        drop(list);
+        assert_eq!(std::rc::Rc::strong_count(&counter), 1);
+    }
+}
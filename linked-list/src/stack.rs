@@ -1,42 +1,130 @@
 type Link<T> = Option<Box<Node<T>>>;
 
-#[allow(dead_code)]
 struct Node<T> {
     val: T,
     next: Link<T>,
 }
 
-#[allow(dead_code)]
-struct Stack<T> {
+pub struct Stack<T> {
     head: Link<T>,
 }
 
-#[allow(dead_code)]
-impl<T> Stack<T>
-where
-    T: Clone,
-{
-    fn new() -> Self {
+impl<T> Stack<T> {
+    pub fn new() -> Self {
         Self { head: None }
     }
 
-    fn peek(&self) -> Option<T> {
-        self.head.as_ref().map(|n| n.val.clone())
+    pub fn peek(&self) -> Option<&T> {
+        self.head.as_ref().map(|n| &n.val)
     }
 
-    fn pop(&mut self) -> Option<T> {
+    pub fn pop(&mut self) -> Option<T> {
         let node = self.head.take()?;
         self.head = node.next;
         Some(node.val)
     }
 
-    fn push(&mut self, val: T) {
+    pub fn push(&mut self, val: T) {
         let node = Some(Box::new(Node {
             val,
             next: self.head.take(),
         }));
         self.head = node;
     }
+
+    /// Iterates from the top to the bottom of the stack.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            next: self.head.as_deref(),
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            next: self.head.as_deref_mut(),
+        }
+    }
+}
+
+impl<T> Default for Stack<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for Stack<T> {
+    fn drop(&mut self) {
+        let mut link = self.head.take();
+        while let Some(mut node) = link {
+            link = node.next.take();
+        }
+    }
+}
+
+pub struct Iter<'a, T> {
+    next: Option<&'a Node<T>>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let node = self.next?;
+        self.next = node.next.as_deref();
+        Some(&node.val)
+    }
+}
+
+pub struct IterMut<'a, T> {
+    next: Option<&'a mut Node<T>>,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<&'a mut T> {
+        let node = self.next.take()?;
+        self.next = node.next.as_deref_mut();
+        Some(&mut node.val)
+    }
+}
+
+/// Pops the values from the top to the bottom.
+pub struct IntoIter<T>(Stack<T>);
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.0.pop()
+    }
+}
+
+impl<T> IntoIterator for Stack<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter(self)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Stack<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut Stack<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> IterMut<'a, T> {
+        self.iter_mut()
+    }
 }
 
 #[cfg(test)]
@@ -49,7 +137,7 @@ mod tests {
 
         assert!(s.peek().is_none());
         s.push(0);
-        assert!(s.peek().is_some_and(|i| i == 0));
+        assert!(s.peek().is_some_and(|i| *i == 0));
         assert!(s.pop().is_some_and(|i| i == 0));
         assert!(s.peek().is_none());
         assert!(s.pop().is_none());
@@ -57,10 +145,25 @@ mod tests {
         s.push(1);
         s.push(2);
         s.push(3);
-        assert!(s.peek().is_some_and(|i| i == 3));
+        assert!(s.peek().is_some_and(|i| *i == 3));
         assert!(s.pop().is_some_and(|i| i == 3));
         assert!(s.pop().is_some_and(|i| i == 2));
         assert!(s.pop().is_some_and(|i| i == 1));
         assert!(s.pop().is_none());
     }
+
+    #[test]
+    fn iterators() {
+        let mut s = Stack::new();
+        for i in 0..3 {
+            s.push(i.to_string());
+        }
+
+        assert!(s.iter().eq(["2", "1", "0"]));
+        for val in &mut s {
+            val.insert(0, '#');
+        }
+        assert!(s.peek().is_some_and(|v| v == "#2"));
+        assert_eq!(s.into_iter().collect::<Vec<_>>(), ["#2", "#1", "#0"]);
+    }
 }
@@ -0,0 +1,274 @@
+use std::{
+    marker::PhantomData,
+    mem::ManuallyDrop,
+    ptr,
+    sync::atomic::{AtomicPtr, Ordering},
+};
+
+use crate::hazard::Domain;
+
+struct Node<T> {
+    /// Moved out by `pop`, so the node is freed without dropping the value.
+    val: ManuallyDrop<T>,
+    next: *mut Node<T>,
+}
+
+/// Stack is a lock-free Treiber stack with the same `push`/`pop`/`peek` as `stack::Stack`,
+/// that can be shared between threads. The head is swapped with compare-and-swap,
+/// and popped nodes are freed through hazard pointers, which also rules out ABA
+/// because a node cannot be reused while a thread holds it.
+pub struct Stack<T> {
+    head: AtomicPtr<Node<T>>,
+    domain: Domain,
+    marker: PhantomData<Box<Node<T>>>,
+}
+
+// SAFETY: values are moved between threads, but never shared,
+// except by `peek`, which copies them
+unsafe impl<T: Send> Send for Stack<T> {}
+unsafe impl<T: Send> Sync for Stack<T> {}
+
+impl<T> Stack<T> {
+    pub fn new() -> Self {
+        Self {
+            head: AtomicPtr::new(ptr::null_mut()),
+            domain: Domain::new(),
+            marker: PhantomData,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.head.load(Ordering::Acquire).is_null()
+    }
+
+    /// Copies the top value.
+    /// The value cannot be borrowed or cloned, because another thread can pop it
+    /// and drop it while it is read. A bitwise copy does not touch anything the value owns.
+    pub fn peek(&self) -> Option<T>
+    where
+        T: Copy,
+    {
+        let guard = self.domain.guard();
+        let head = guard.protect(&self.head);
+        // SAFETY: the guard keeps the node from being freed
+        (!head.is_null()).then(|| unsafe { *(*head).val })
+    }
+
+    pub fn pop(&self) -> Option<T> {
+        let guard = self.domain.guard();
+        loop {
+            let head = guard.protect(&self.head);
+            if head.is_null() {
+                return None;
+            }
+
+            // SAFETY: the guard keeps the node from being freed
+            let next = unsafe { (*head).next };
+            if self
+                .head
+                .compare_exchange(head, next, Ordering::AcqRel, Ordering::Acquire)
+                .is_ok()
+            {
+                guard.reset();
+                // SAFETY: only the thread that unlinked the node takes the value,
+                // and the node is retired once
+                unsafe {
+                    let val = ptr::read(&*(*head).val);
+                    self.domain.retire(head);
+                    return Some(val);
+                }
+            }
+        }
+    }
+
+    pub fn push(&self, val: T) {
+        let node = Box::into_raw(Box::new(Node {
+            val: ManuallyDrop::new(val),
+            next: ptr::null_mut(),
+        }));
+
+        let mut head = self.head.load(Ordering::Relaxed);
+        loop {
+            // SAFETY: the node is not shared before the exchange succeeds
+            unsafe { (*node).next = head };
+            match self
+                .head
+                .compare_exchange_weak(head, node, Ordering::Release, Ordering::Relaxed)
+            {
+                Ok(_) => return,
+                Err(new) => head = new,
+            }
+        }
+    }
+}
+
+impl<T> Default for Stack<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Drops the values left on the stack, popped nodes are freed by the domain.
+impl<T> Drop for Stack<T> {
+    fn drop(&mut self) {
+        let mut curr = *self.head.get_mut();
+        while !curr.is_null() {
+            // SAFETY: with `&mut self` the stack is the only owner of its nodes
+            let mut node = unsafe { Box::from_raw(curr) };
+            // SAFETY: the value of a linked node was not moved out
+            unsafe { ManuallyDrop::drop(&mut node.val) };
+            curr = node.next;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashSet,
+        sync::{Arc, Barrier},
+        thread,
+    };
+
+    use super::*;
+
+    const THREADS: usize = 4;
+    const PER_THREAD: usize = 10_000;
+
+    #[test]
+    fn it_works() {
+        let s = Stack::new();
+        assert!(s.peek().is_none());
+        assert!(s.pop().is_none());
+
+        s.push(1);
+        s.push(2);
+        s.push(3);
+        assert_eq!(s.peek(), Some(3));
+        assert_eq!(s.pop(), Some(3));
+        assert_eq!(s.pop(), Some(2));
+        s.push(4);
+        assert_eq!(s.pop(), Some(4));
+        assert_eq!(s.pop(), Some(1));
+        assert!(s.is_empty());
+    }
+
+    #[test]
+    fn drops_values() {
+        let counter = Arc::new(());
+        let s = Stack::new();
+        for _ in 0..200 {
+            s.push(Arc::clone(&counter));
+        }
+        for _ in 0..150 {
+            s.pop();
+        }
+        assert_eq!(Arc::strong_count(&counter), 51);
+
+        drop(s);
+        assert_eq!(Arc::strong_count(&counter), 1);
+    }
+
+    #[test]
+    fn concurrent_push_pop() {
+        let s = Arc::new(Stack::new());
+        let barrier = Arc::new(Barrier::new(THREADS * 2));
+
+        let pushers: Vec<_> = (0..THREADS)
+            .map(|t| {
+                let s = Arc::clone(&s);
+                let barrier = Arc::clone(&barrier);
+                thread::spawn(move || {
+                    barrier.wait();
+                    for i in 0..PER_THREAD {
+                        s.push(t * PER_THREAD + i);
+                    }
+                })
+            })
+            .collect();
+        let poppers: Vec<_> = (0..THREADS)
+            .map(|_| {
+                let s = Arc::clone(&s);
+                let barrier = Arc::clone(&barrier);
+                thread::spawn(move || {
+                    barrier.wait();
+                    let mut popped = Vec::new();
+                    for _ in 0..PER_THREAD {
+                        if let Some(val) = s.pop() {
+                            popped.push(val);
+                        }
+                        s.peek();
+                    }
+                    popped
+                })
+            })
+            .collect();
+
+        for pusher in pushers {
+            pusher.join().unwrap();
+        }
+        let mut seen = HashSet::new();
+        for popper in poppers {
+            for val in popper.join().unwrap() {
+                assert!(seen.insert(val), "{val} was popped twice");
+            }
+        }
+        while let Some(val) = s.pop() {
+            assert!(seen.insert(val), "{val} was popped twice");
+        }
+        assert_eq!(seen.len(), THREADS * PER_THREAD);
+    }
+
+    #[test]
+    fn keeps_order_per_thread() {
+        let s = Arc::new(Stack::new());
+        let handles: Vec<_> = (0..THREADS)
+            .map(|t| {
+                let s = Arc::clone(&s);
+                thread::spawn(move || {
+                    for i in 0..PER_THREAD {
+                        s.push((t, i));
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        // values of one thread come off in reverse order
+        let mut last = [usize::MAX; THREADS];
+        while let Some((t, i)) = s.pop() {
+            assert!(i < last[t]);
+            last[t] = i;
+        }
+        assert_eq!(last, [0; THREADS]);
+    }
+
+    #[test]
+    fn concurrent_drops() {
+        let counter = Arc::new(());
+        let s = Arc::new(Stack::new());
+        let handles: Vec<_> = (0..THREADS)
+            .map(|_| {
+                let s = Arc::clone(&s);
+                let counter = Arc::clone(&counter);
+                thread::spawn(move || {
+                    for i in 0..PER_THREAD {
+                        s.push(Arc::clone(&counter));
+                        if i % 2 == 0 {
+                            s.pop();
+                        }
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(Arc::strong_count(&counter), 1 + THREADS * PER_THREAD / 2);
+        drop(s);
+        assert_eq!(Arc::strong_count(&counter), 1);
+    }
+}
@@ -0,0 +1,462 @@
+use std::fmt::Display;
+
+use anyhow::{anyhow, Error};
+
+/// Elements per node.
+const CAP: usize = 64;
+/// Nodes with fewer elements are merged with or refilled from their successor.
+const MIN: usize = CAP / 4;
+
+type Link<T> = Option<Box<Node<T>>>;
+
+struct Node<T> {
+    /// Never empty and never longer than `CAP`.
+    vals: Vec<T>,
+    next: Link<T>,
+}
+
+impl<T> Node<T> {
+    fn new(next: Link<T>) -> Box<Self> {
+        Box::new(Self {
+            vals: Vec::with_capacity(CAP),
+            next,
+        })
+    }
+}
+
+/// List has the same API as `single::List`, but every node holds up to `CAP` elements.
+/// Walking to an index skips whole nodes, so it touches `CAP` times fewer nodes,
+/// and the elements of a node are next to each other in memory.
+/// A full node is split in halves, and a node that falls below `MIN` elements
+/// takes elements from its successor or is merged with it.
+pub struct List<T> {
+    len: usize,
+    head: Link<T>,
+}
+
+impl<T> List<T> {
+    pub fn new() -> Self {
+        Self { len: 0, head: None }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn push(&mut self, val: T) {
+        self.insert_at(0, val)
+            .expect("the front is always in bounds");
+    }
+
+    pub fn pop(&mut self) -> Result<T, Error> {
+        if self.len == 0 {
+            return Err(anyhow!("empty list"));
+        }
+        self.delete_at(0)
+    }
+
+    pub fn get_at(&self, i: usize) -> Result<&T, Error> {
+        if i >= self.len {
+            return Err(anyhow!("index {i} is out of bounds"));
+        }
+
+        let mut i = i;
+        let mut node = self.head.as_deref().expect("the list is not empty");
+        while i >= node.vals.len() {
+            i -= node.vals.len();
+            node = node.next.as_deref().expect("the list is longer than i");
+        }
+        Ok(&node.vals[i])
+    }
+
+    pub fn get_at_mut(&mut self, i: usize) -> Result<&mut T, Error> {
+        if i >= self.len {
+            return Err(anyhow!("index {i} is out of bounds"));
+        }
+
+        let mut i = i;
+        let mut node = self.head.as_deref_mut().expect("the list is not empty");
+        while i >= node.vals.len() {
+            i -= node.vals.len();
+            node = node.next.as_deref_mut().expect("the list is longer than i");
+        }
+        Ok(&mut node.vals[i])
+    }
+
+    pub fn delete_at(&mut self, i: usize) -> Result<T, Error> {
+        if i >= self.len {
+            return Err(anyhow!("index {i} is out of bounds"));
+        }
+
+        let mut i = i;
+        let mut link = &mut self.head;
+        while link.as_ref().is_some_and(|node| i >= node.vals.len()) {
+            i -= link.as_ref().map_or(0, |node| node.vals.len());
+            link = &mut link.as_mut().expect("checked by the loop").next;
+        }
+
+        let node = link.as_mut().expect("the list is longer than i");
+        let val = node.vals.remove(i);
+        if node.vals.len() < MIN {
+            if let Some(mut next) = node.next.take() {
+                if node.vals.len() + next.vals.len() <= CAP {
+                    node.vals.append(&mut next.vals);
+                    node.next = next.next.take();
+                } else {
+                    let n = (next.vals.len() - node.vals.len()) / 2;
+                    node.vals.extend(next.vals.drain(..n));
+                    node.next = Some(next);
+                }
+            }
+            if node.vals.is_empty() {
+                *link = node.next.take();
+            }
+        }
+
+        self.len -= 1;
+        Ok(val)
+    }
+
+    pub fn insert_at(&mut self, i: usize, val: T) -> Result<(), Error> {
+        if i > self.len {
+            return Err(anyhow!("index {i} is out of bounds"));
+        }
+
+        let mut i = i;
+        let mut node = self.head.get_or_insert_with(|| Node::new(None));
+        while i > node.vals.len() {
+            i -= node.vals.len();
+            node = node.next.as_mut().expect("the list is at least i long");
+        }
+
+        if node.vals.len() == CAP {
+            let mut back = Node::new(node.next.take());
+            back.vals.extend(node.vals.drain(CAP / 2..));
+            node.next = Some(back);
+            if i > CAP / 2 {
+                i -= CAP / 2;
+                node = node.next.as_mut().expect("the back half was just linked");
+            }
+        }
+        node.vals.insert(i, val);
+
+        self.len += 1;
+        Ok(())
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            next: self.head.as_deref(),
+            vals: [].iter(),
+            len: self.len,
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            len: self.len,
+            next: self.head.as_deref_mut(),
+            vals: [].iter_mut(),
+        }
+    }
+}
+
+impl<T> Default for List<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for List<T> {
+    fn drop(&mut self) {
+        let mut link = self.head.take();
+        while let Some(mut node) = link {
+            link = node.next.take();
+        }
+    }
+}
+
+/// Iter hands out the values of one node before it moves on to the next node.
+pub struct Iter<'a, T> {
+    next: Option<&'a Node<T>>,
+    vals: std::slice::Iter<'a, T>,
+    len: usize,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        loop {
+            if let Some(val) = self.vals.next() {
+                self.len -= 1;
+                return Some(val);
+            }
+            let node = self.next?;
+            self.vals = node.vals.iter();
+            self.next = node.next.as_deref();
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+pub struct IterMut<'a, T> {
+    next: Option<&'a mut Node<T>>,
+    vals: std::slice::IterMut<'a, T>,
+    len: usize,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<&'a mut T> {
+        loop {
+            if let Some(val) = self.vals.next() {
+                self.len -= 1;
+                return Some(val);
+            }
+            let Node { vals, next } = self.next.take()?;
+            self.vals = vals.iter_mut();
+            self.next = next.as_deref_mut();
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> ExactSizeIterator for IterMut<'_, T> {}
+
+pub struct IntoIter<T>(List<T>);
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.0.pop().ok()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0.len, Some(self.0.len))
+    }
+}
+
+impl<T> IntoIterator for List<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter(self)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a List<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut List<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+impl<T> Display for List<T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("[")?;
+        for (i, val) in self.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            f.write_fmt(format_args!("{}", val))?;
+        }
+        f.write_str("]")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+
+    use super::*;
+
+    /// Checks that no node is empty or over capacity, and that `len` matches.
+    fn nodes_are_valid<T>(l: &List<T>) -> bool {
+        let mut len = 0;
+        let mut node = l.head.as_deref();
+        while let Some(n) = node {
+            if n.vals.is_empty() || n.vals.len() > CAP {
+                return false;
+            }
+            len += n.vals.len();
+            node = n.next.as_deref();
+        }
+        len == l.len
+    }
+
+    #[test]
+    fn insert_at() {
+        let mut l: List<i32> = List::new();
+        l.push(2);
+        l.push(1);
+        l.push(0);
+        assert!(l.insert_at(1, -1).is_ok());
+        assert!(l.insert_at(4, 3).is_ok());
+        assert!(l.insert_at(6, 3).is_err());
+        assert!(l.get_at(1).is_ok_and(|i| *i == -1));
+        assert!(l.get_at(0).is_ok_and(|i| *i == 0));
+        assert!(l.get_at(3).is_ok_and(|i| *i == 2));
+        assert!(l.get_at(4).is_ok_and(|i| *i == 3));
+    }
+
+    #[test]
+    fn delete_at() {
+        let mut l: List<i32> = List::new();
+        l.push(2);
+        l.push(1);
+        l.push(0);
+        assert!(l.delete_at(1).is_ok_and(|i| i == 1));
+        assert!(l.delete_at(3).is_err());
+        assert!(l.get_at(0).is_ok_and(|i| *i == 0));
+        assert!(l.get_at(1).is_ok_and(|i| *i == 2));
+        assert!(l.pop().is_ok_and(|i| i == 0));
+        assert!(l.pop().is_ok_and(|i| i == 2));
+        assert!(l.pop().is_err());
+        assert!(l.head.is_none());
+    }
+
+    #[test]
+    fn splits_and_merges() {
+        let mut l = List::new();
+        for i in 0..CAP * 4 {
+            l.insert_at(i, i).unwrap();
+        }
+        assert!(nodes_are_valid(&l));
+        assert!(l.iter().copied().eq(0..CAP * 4));
+
+        for _ in 0..CAP * 3 {
+            l.delete_at(CAP / 2).unwrap();
+        }
+        assert!(nodes_are_valid(&l));
+        assert!(l
+            .iter()
+            .copied()
+            .eq((0..CAP / 2).chain(CAP * 3 + CAP / 2..CAP * 4)));
+    }
+
+    #[test]
+    fn matches_vec() {
+        let mut rng = rand::thread_rng();
+        let mut l = List::new();
+        let mut v = Vec::new();
+
+        for n in 0..5000 {
+            if !v.is_empty() && rng.gen_bool(0.4) {
+                let i = rng.gen_range(0..v.len());
+                assert_eq!(l.delete_at(i).unwrap(), v.remove(i));
+            } else {
+                let i = rng.gen_range(0..=v.len());
+                l.insert_at(i, n).unwrap();
+                v.insert(i, n);
+            }
+        }
+
+        assert!(nodes_are_valid(&l));
+        assert!(l.iter().eq(v.iter()));
+        for (i, val) in v.iter().enumerate().step_by(37) {
+            assert!(l.get_at(i).is_ok_and(|x| x == val));
+        }
+    }
+
+    #[test]
+    fn iterators() {
+        let mut l = List::new();
+        for i in 0..4 {
+            l.push(i.to_string());
+        }
+
+        assert!(l.iter().eq(["3", "2", "1", "0"]));
+        for val in &mut l {
+            val.push('!');
+        }
+        l.get_at_mut(0).unwrap().push('?');
+        assert_eq!(l.to_string(), "[3!?, 2!, 1!, 0!]");
+        assert_eq!(l.iter_mut().len(), 4);
+        assert_eq!(l.into_iter().collect::<Vec<_>>(), ["3!?", "2!", "1!", "0!"]);
+    }
+
+    #[test]
+    fn long_list() {
+        let mut l = List::new();
+        for i in 0..1_000_000 {
+            l.push(i);
+        }
+        assert!(l.get_at(999_999).is_ok_and(|i| *i == 0));
+        assert!(l.delete_at(500_000).is_ok_and(|i| i == 499_999));
+        assert!(l.insert_at(999_999, -1).is_ok());
+        assert!(l.get_at(999_998).is_ok_and(|i| *i == 0));
+        assert!(l.get_at(999_999).is_ok_and(|i| *i == -1));
+        assert!(nodes_are_valid(&l));
+    }
+
+    #[cfg(feature = "bench")]
+    mod bench {
+        use super::*;
+
+        extern crate test;
+        use test::{black_box, Bencher};
+
+        use crate::single;
+
+        const BENCH_LEN: usize = 10_000;
+
+        #[bench]
+        fn bench_unrolled_get_at(b: &mut Bencher) {
+            let mut l = List::new();
+            for i in 0..BENCH_LEN {
+                l.push(i);
+            }
+            b.iter(|| {
+                (0..BENCH_LEN)
+                    .step_by(BENCH_LEN / 10)
+                    .map(|i| *l.get_at(black_box(i)).unwrap())
+                    .sum::<usize>()
+            })
+        }
+
+        #[bench]
+        fn bench_single_get_at(b: &mut Bencher) {
+            let mut l = single::List::new();
+            for i in 0..BENCH_LEN {
+                l.push(i);
+            }
+            b.iter(|| {
+                (0..BENCH_LEN)
+                    .step_by(BENCH_LEN / 10)
+                    .map(|i| *l.get_at(black_box(i)).unwrap())
+                    .sum::<usize>()
+            })
+        }
+    }
+}
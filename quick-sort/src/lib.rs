@@ -40,6 +40,7 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use array_list::ArrayList;
     use rand::Rng;
 
     fn is_sorted<T>(a: &[T]) -> bool
@@ -75,4 +76,13 @@ mod tests {
         quick_sort(&mut a);
         assert!(is_sorted(&a));
     }
+
+    #[test]
+    fn array_list() {
+        let mut rng = rand::thread_rng();
+        let mut a: ArrayList<i32> = (0..100).map(|_| rng.gen()).collect();
+
+        quick_sort(&mut a);
+        assert!(is_sorted(&a));
+    }
 }